@@ -0,0 +1,73 @@
+#!/usr/bin/env python3
+"""Decode a JD79661 frame dump (see `DisplayBuffer::dump_frame`) into a PNG.
+
+Usage:
+    decode_frame_dump.py SOURCE OUTPUT.png
+
+SOURCE is either a capture file or a serial port. For a serial port, a byte
+is sent first to request a dump (as `examples/jd79661_frame_dump.rs` expects)
+and pyserial must be installed. Pillow is needed to write the PNG.
+"""
+
+import struct
+import sys
+
+from PIL import Image
+
+MAGIC = b"JD79"
+HEADER = struct.Struct("<4sHHI")
+SOURCES = 128
+
+# RAM codes, see QuadColor::to_u8
+COLORS = {
+    0: (0, 0, 0),  # Black
+    1: (255, 255, 255),  # White
+    2: (255, 255, 0),  # Yellow
+    3: (255, 0, 0),  # Red
+}
+
+
+def read_dump(source):
+    if source.startswith("/dev/") or source.upper().startswith("COM"):
+        import serial
+
+        with serial.Serial(source, 115200, timeout=5) as port:
+            port.reset_input_buffer()
+            port.write(b"d")
+            data = port.read_until(MAGIC)
+            if not data.endswith(MAGIC):
+                sys.exit("no frame dump received")
+            rest = port.read(HEADER.size - len(MAGIC))
+            _, width, height, length = HEADER.unpack(MAGIC + rest)
+            return width, height, port.read(length)
+
+    with open(source, "rb") as f:
+        data = f.read()
+    start = data.find(MAGIC)
+    if start < 0:
+        sys.exit("no frame dump found in " + source)
+    _, width, height, length = HEADER.unpack_from(data, start)
+    body = start + HEADER.size
+    return width, height, data[body : body + length]
+
+
+def main():
+    if len(sys.argv) != 3:
+        sys.exit(__doc__)
+    width, height, frame = read_dump(sys.argv[1])
+    if len(frame) != width * SOURCES // 4:
+        sys.exit("truncated frame: got %d bytes" % len(frame))
+
+    image = Image.new("RGB", (width, height))
+    row_bytes = SOURCES // 4
+    for x in range(width):
+        line = frame[x * row_bytes : (x + 1) * row_bytes]
+        for n in range(height):
+            code = (line[n // 4] >> (6 - 2 * (n % 4))) & 0b11
+            image.putpixel((x, height - 1 - n), COLORS[code])
+    image.save(sys.argv[2])
+    print("wrote %dx%d image to %s" % (width, height, sys.argv[2]))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,150 @@
+//! Streams a rendered JD79661 frame to the host over USB serial, for checking
+//! on-device rendering without a camera.
+//!
+//! Draws a demo scene into a `DisplayBuffer` and, whenever a byte arrives on
+//! the serial port, sends it as a `DisplayBuffer::dump_frame` dump: a 12 byte
+//! header (`JD79` magic, width, height, length) followed by the 8000 byte
+//! packed frame. `examples/decode_frame_dump.py` turns a capture into a PNG.
+//!
+//! # How to use:
+//!
+//! 1. Put the board in BOOTSEL mode (hold BOOT, press RESET).
+//!
+//! 2. Flash and run:
+//!    `cargo run --example jd79661_frame_dump`
+//!
+//! 3. Request a dump and decode it on the host:
+//!    `python3 examples/decode_frame_dump.py /dev/cu.usbmodem* frame.png`
+
+#![no_std]
+#![no_main]
+
+use adafruit_feather_rp2040 as bsp;
+use bsp::entry;
+use bsp::hal::{
+    clocks::{init_clocks_and_plls, Clock},
+    pac,
+    usb::UsbBus,
+    watchdog::Watchdog,
+    Sio,
+};
+use bsp::{Pins, XOSC_CRYSTAL_FREQ};
+
+use defmt_rtt as _; // Link defmt-rtt so panic-probe can use it for crash reports
+use panic_probe as _;
+
+use usb_device::class_prelude::*;
+use usb_device::prelude::*;
+use usbd_serial::SerialPort;
+
+use adafruit_feather_thinkink_discovery::{
+    DisplayBuffer, QuadColor, FRAME_DUMP_HEADER_LEN, RAM_BYTES,
+};
+
+use embedded_graphics::mono_font::ascii::FONT_10X20;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
+
+/// Size of one complete dump.
+const DUMP_LEN: usize = FRAME_DUMP_HEADER_LEN + RAM_BYTES;
+
+#[entry]
+fn main() -> ! {
+    let mut pac = pac::Peripherals::take().unwrap();
+    let core = pac::CorePeripherals::take().unwrap();
+
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+
+    let clocks = init_clocks_and_plls(
+        XOSC_CRYSTAL_FREQ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    let _delay = cortex_m::delay::Delay::new(core.SYST, clocks.system_clock.freq().to_Hz());
+
+    let sio = Sio::new(pac.SIO);
+
+    let mut resets = pac.RESETS;
+    let _pins = Pins::new(pac.IO_BANK0, pac.PADS_BANK0, sio.gpio_bank0, &mut resets);
+
+    // SAFETY: We use singletons to ensure these stay alive for the duration of the program.
+    let usb_bus =
+        cortex_m::singleton!(: UsbBusAllocator<UsbBus> = UsbBusAllocator::new(UsbBus::new(
+            pac.USBCTRL_REGS,
+            pac.USBCTRL_DPRAM,
+            clocks.usb_clock,
+            true,
+            &mut resets,
+        )))
+        .unwrap();
+
+    let mut serial = SerialPort::new(usb_bus);
+
+    let mut usb_dev = UsbDeviceBuilder::new(usb_bus, UsbVidPid(0x16c0, 0x27dd))
+        .strings(&[StringDescriptors::default()
+            .manufacturer("Adafruit")
+            .product("Feather RP2040 Frame Dump")
+            .serial_number("DUMP1")])
+        .unwrap()
+        .device_class(2)
+        .build();
+
+    let mut display = DisplayBuffer::new();
+    draw_scene(&mut display);
+
+    // The USB endpoint takes 64 bytes at a time, so the dump is staged in RAM
+    // and sent as the host drains it.
+    let dump = cortex_m::singleton!(: [u8; DUMP_LEN] = [0; DUMP_LEN]).unwrap();
+    let mut len = 0;
+    let _ = display.dump_frame(|bytes| {
+        dump[len..len + bytes.len()].copy_from_slice(bytes);
+        len += bytes.len();
+        Ok::<(), ()>(())
+    });
+
+    let mut sent = DUMP_LEN;
+    loop {
+        watchdog.feed();
+
+        if usb_dev.poll(&mut [&mut serial]) {
+            let mut buf = [0u8; 64];
+            if let Ok(count) = serial.read(&mut buf) {
+                if count > 0 && sent == DUMP_LEN {
+                    sent = 0;
+                }
+            }
+        }
+
+        if sent < DUMP_LEN {
+            if let Ok(count) = serial.write(&dump[sent..]) {
+                sent += count;
+            }
+        }
+    }
+}
+
+fn draw_scene(display: &mut DisplayBuffer) {
+    let style = MonoTextStyle::new(&FONT_10X20, QuadColor::Black);
+    display.draw_centered_text("Frame dump", 8, style);
+
+    Rectangle::new(Point::new(20, 50), Size::new(60, 50))
+        .into_styled(PrimitiveStyle::with_fill(QuadColor::Red))
+        .draw(display)
+        .unwrap();
+    Circle::new(Point::new(100, 45), 60)
+        .into_styled(PrimitiveStyle::with_fill(QuadColor::Yellow))
+        .draw(display)
+        .unwrap();
+    Rectangle::new(Point::new(180, 50), Size::new(50, 50))
+        .into_styled(PrimitiveStyle::with_stroke(QuadColor::Black, 3))
+        .draw(display)
+        .unwrap();
+}
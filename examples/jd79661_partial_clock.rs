@@ -0,0 +1,158 @@
+//! Partial-refresh clock for the Adafruit RP2040 Feather ThinkInk with the
+//! 2.13" JD79661 quad-color e-paper display
+//! ([Product 6373](https://www.adafruit.com/product/6373)).
+//!
+//! Shows the time since boot as `MM:SS`, updated every 10 seconds. Each tick
+//! redraws the next frame and hands both frames to `update_diff`, which
+//! refreshes only the bounding box of the changed digits. Every 5th update is
+//! a full refresh instead, to clear the ghosting partial refreshes leave
+//! behind.
+//!
+//! Connections (Integrated/FPC):
+//!
+//! | Pin         | GPIO  | Function |
+//! |-------------|-------|----------|
+//! | EPD_SCK     | GP22  | SCK      |
+//! | EPD_MOSI    | GP23  | MOSI     |
+//! | EPD_CS      | GP19  | CS       |
+//! | EPD_BUSY    | GP16  | BUSY     |
+//! | EPD_DC      | GP18  | DC       |
+//! | EPD_RESET   | GP17  | RESET    |
+//!
+//! To run this example run:
+//! `cargo run --example jd79661_partial_clock`
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use adafruit_feather_rp2040 as bsp;
+use bsp::hal::clocks::init_clocks_and_plls;
+use bsp::hal::fugit::RateExtU32;
+use bsp::hal::gpio::{FunctionSpi, Pins};
+use bsp::hal::{spi, Clock, Sio, Watchdog};
+use bsp::{entry, pac};
+use defmt::{info, println};
+use defmt_rtt as _;
+use panic_probe as _;
+
+use embedded_hal_bus::spi::ExclusiveDevice;
+
+use adafruit_feather_thinkink_discovery::{Builder, DisplayBuffer, QuadColor};
+
+use embedded_graphics::mono_font::ascii::{FONT_10X20, FONT_6X9};
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Text;
+use embedded_hal::delay::DelayNs;
+use heapless::String;
+
+use bsp::hal::Timer;
+
+/// Seconds between clock updates.
+const TICK_S: u64 = 10;
+
+#[entry]
+fn main() -> ! {
+    info!("Program start");
+    let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+    let sio = Sio::new(pac.SIO);
+
+    let external_xtal_freq_hz = 12_000_000u32;
+    let clocks = init_clocks_and_plls(
+        external_xtal_freq_hz,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    let mut delay = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+
+    let pins = Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    // ThinkInk E-Ink Connections:
+    let sck = pins.gpio22.into_function::<FunctionSpi>();
+    let mosi = pins.gpio23.into_function::<FunctionSpi>();
+    let miso = pins.gpio20.into_function::<FunctionSpi>();
+
+    let cs = pins.gpio19.into_push_pull_output();
+    let dc = pins.gpio18.into_push_pull_output();
+    let rst = pins.gpio17.into_push_pull_output();
+    let busy = pins.gpio16.into_pull_down_input();
+    let dummy_cs = pins.gpio15.into_push_pull_output();
+
+    let spi = spi::Spi::<_, _, _, 8>::new(pac.SPI0, (mosi, miso, sck)).init(
+        &mut pac.RESETS,
+        clocks.peripheral_clock.freq(),
+        4_000_000u32.Hz(),
+        embedded_hal::spi::MODE_0,
+    );
+
+    let mut spi_device = ExclusiveDevice::new_no_delay(spi, dummy_cs).unwrap();
+
+    // Every 5th update is a full refresh to clear ghosting.
+    let mut epd = Builder::new()
+        .full_refresh_every(4)
+        .init(&mut spi_device, cs, busy, dc, rst, &mut delay)
+        .unwrap();
+
+    // `shown` mirrors what is on the panel, `next` is drawn from scratch each tick.
+    let mut shown = DisplayBuffer::new();
+    let mut next = DisplayBuffer::new();
+
+    draw_clock(&mut shown, 0);
+    epd.update_frames(&mut spi_device, &shown).unwrap();
+    epd.display_frame(&mut spi_device, &mut delay).unwrap();
+
+    let start = delay.get_counter();
+    let mut tick = 0;
+    loop {
+        tick += 1;
+        let due_us = tick * TICK_S * 1_000_000;
+        let elapsed_us = (delay.get_counter() - start).to_micros();
+        if due_us > elapsed_us {
+            delay.delay_us((due_us - elapsed_us) as u32);
+        }
+
+        next.clear();
+        draw_clock(&mut next, tick * TICK_S);
+
+        let partials = epd.partials_since_full();
+        epd.update_diff(&mut spi_device, &shown, &next, &mut delay)
+            .unwrap();
+        if epd.partials_since_full() > partials {
+            println!("Partial refresh ({} since full)", epd.partials_since_full());
+        } else {
+            println!("Full refresh");
+        }
+
+        core::mem::swap(&mut shown, &mut next);
+    }
+}
+
+fn draw_clock(display: &mut DisplayBuffer, seconds: u64) {
+    let mut time: String<8> = String::new();
+    let _ = write!(time, "{:02}:{:02}", seconds / 60 % 100, seconds % 60);
+
+    let big = MonoTextStyle::new(&FONT_10X20, QuadColor::Black);
+    let small = MonoTextStyle::new(&FONT_6X9, QuadColor::Red);
+
+    Text::new("Uptime", Point::new(100, 40), small)
+        .draw(display)
+        .unwrap();
+    Text::new(&time, Point::new(100, 70), big)
+        .draw(display)
+        .unwrap();
+}
@@ -0,0 +1,219 @@
+//! BME280 weather dashboard on the Adafruit RP2040 Feather ThinkInk with the
+//! 2.13" JD79661 quad-color e-paper display
+//! ([Product 6373](https://www.adafruit.com/product/6373)).
+//!
+//! Reads temperature, humidity and pressure from a BME280 on the STEMMA QT
+//! port every few minutes and shows them on the panel, with readings outside
+//! a comfortable range highlighted in red (hot) or yellow (humid). The panel
+//! keeps the image unpowered, so it is put into deep sleep between updates
+//! and woken for the next one, and a `RefreshLimiter` guards against
+//! refreshing too often.
+//!
+//! Connections (Integrated/FPC):
+//!
+//! | Pin         | GPIO  | Function |
+//! |-------------|-------|----------|
+//! | EPD_SCK     | GP22  | SCK      |
+//! | EPD_MOSI    | GP23  | MOSI     |
+//! | EPD_CS      | GP19  | CS       |
+//! | EPD_BUSY    | GP16  | BUSY     |
+//! | EPD_DC      | GP18  | DC       |
+//! | EPD_RESET   | GP17  | RESET    |
+//!
+//! BME280 on STEMMA QT (I2C1, address 0x77): SDA on GP2, SCL on GP3.
+//!
+//! To run this example run:
+//! `cargo run --example jd79661_sensor_dashboard`
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use adafruit_feather_rp2040 as bsp;
+use bsp::hal::clocks::init_clocks_and_plls;
+use bsp::hal::fugit::RateExtU32;
+use bsp::hal::gpio::{FunctionI2C, FunctionSpi, Pins, PullUp};
+use bsp::hal::{spi, Clock, Sio, Timer, Watchdog, I2C};
+use bsp::{entry, pac};
+use defmt::{error, info};
+use defmt_rtt as _;
+use panic_probe as _;
+
+use bme280::i2c::BME280;
+use embedded_hal_bus::spi::ExclusiveDevice;
+
+use adafruit_feather_thinkink_discovery::{
+    DisplayBuffer, Jd79661, Jd79661Error, PowerState, QuadColor, RefreshLimiter,
+};
+
+use embedded_graphics::mono_font::ascii::{FONT_10X20, FONT_6X10};
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Text;
+use embedded_hal::delay::DelayNs;
+use heapless::String;
+
+/// Seconds between dashboard updates.
+const UPDATE_S: u32 = 180;
+
+/// Temperature (°C) from which the reading is shown in red.
+const HOT_C: f32 = 28.0;
+/// Relative humidity (%) from which the reading is shown in yellow.
+const HUMID_PCT: f32 = 70.0;
+
+#[entry]
+fn main() -> ! {
+    info!("Program start");
+    let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+    let sio = Sio::new(pac.SIO);
+
+    let external_xtal_freq_hz = 12_000_000u32;
+    let clocks = init_clocks_and_plls(
+        external_xtal_freq_hz,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    let mut delay = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+
+    let pins = Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    // STEMMA QT (I2C1)
+    let sda = pins
+        .gpio2
+        .into_pull_type::<PullUp>()
+        .into_function::<FunctionI2C>();
+    let scl = pins
+        .gpio3
+        .into_pull_type::<PullUp>()
+        .into_function::<FunctionI2C>();
+    let i2c = I2C::i2c1(
+        pac.I2C1,
+        sda,
+        scl,
+        400_000u32.Hz(),
+        &mut pac.RESETS,
+        &clocks.system_clock,
+    );
+    let mut bme280 = BME280::new_secondary(i2c);
+    if let Err(e) = bme280.init(&mut delay) {
+        error!("Failed to initialize BME280: {:?}", defmt::Debug2Format(&e));
+    }
+
+    // ThinkInk E-Ink Connections:
+    let sck = pins.gpio22.into_function::<FunctionSpi>();
+    let mosi = pins.gpio23.into_function::<FunctionSpi>();
+    let miso = pins.gpio20.into_function::<FunctionSpi>();
+
+    let cs = pins.gpio19.into_push_pull_output();
+    let dc = pins.gpio18.into_push_pull_output();
+    let rst = pins.gpio17.into_push_pull_output();
+    let busy = pins.gpio16.into_pull_down_input();
+    let dummy_cs = pins.gpio15.into_push_pull_output();
+
+    let spi = spi::Spi::<_, _, _, 8>::new(pac.SPI0, (mosi, miso, sck)).init(
+        &mut pac.RESETS,
+        clocks.peripheral_clock.freq(),
+        4_000_000u32.Hz(),
+        embedded_hal::spi::MODE_0,
+    );
+
+    let mut spi_device = ExclusiveDevice::new_no_delay(spi, dummy_cs).unwrap();
+
+    let mut epd = Jd79661::new(&mut spi_device, cs, busy, dc, rst, &mut delay).unwrap();
+    let mut display = DisplayBuffer::new();
+    // Never refresh more than once a minute, even if the loop below is changed.
+    let mut limiter = RefreshLimiter::new(60_000);
+
+    loop {
+        display.clear();
+        match bme280.measure(&mut delay) {
+            Ok(m) => {
+                info!(
+                    "Temperature: {} C, Pressure: {} hPa, Humidity: {} %",
+                    m.temperature,
+                    m.pressure / 100.0,
+                    m.humidity
+                );
+                draw_dashboard(&mut display, m.temperature, m.humidity, m.pressure / 100.0);
+            }
+            Err(e) => {
+                error!("Error reading BME280 sensor: {:?}", defmt::Debug2Format(&e));
+                let style = MonoTextStyle::new(&FONT_10X20, QuadColor::Red);
+                display.draw_centered_text("Sensor error", 50, style);
+            }
+        }
+
+        if epd.power_state() == PowerState::DeepSleep {
+            epd.wake(&mut spi_device, &mut delay).unwrap();
+        }
+        epd.update_frames(&mut spi_device, &display).unwrap();
+        let now_ms = (delay.get_counter().ticks() / 1_000) as u32;
+        match epd.display_frame_limited(&mut spi_device, &mut delay, &mut limiter, now_ms) {
+            Ok(()) => info!("Dashboard refreshed"),
+            Err(Jd79661Error::TooSoon { remaining_ms }) => {
+                info!("Skipping refresh, next allowed in {} ms", remaining_ms)
+            }
+            Err(e) => error!("Refresh failed: {:?}", defmt::Debug2Format(&e)),
+        }
+        // The image stays without power; sleep until the next reading.
+        epd.sleep(&mut spi_device, &mut delay).unwrap();
+
+        delay.delay_ms(UPDATE_S * 1_000);
+    }
+}
+
+fn draw_dashboard(display: &mut DisplayBuffer, temperature: f32, humidity: f32, pressure: f32) {
+    let title = MonoTextStyle::new(&FONT_6X10, QuadColor::Black);
+    display.draw_centered_text("Weather", 4, title);
+
+    let temp_color = if temperature >= HOT_C {
+        QuadColor::Red
+    } else {
+        QuadColor::Black
+    };
+    let humid_color = if humidity >= HUMID_PCT {
+        QuadColor::Yellow
+    } else {
+        QuadColor::Black
+    };
+
+    let mut line: String<24> = String::new();
+    let _ = write!(line, "{:.1} C", temperature);
+    draw_reading(display, "Temp", &line, 40, temp_color);
+
+    line.clear();
+    let _ = write!(line, "{:.0} %", humidity);
+    draw_reading(display, "Humidity", &line, 70, humid_color);
+
+    line.clear();
+    let _ = write!(line, "{:.0} hPa", pressure);
+    draw_reading(display, "Pressure", &line, 100, QuadColor::Black);
+}
+
+/// Draws a small label with the value in large type to its right, both on
+/// the baseline `y`.
+fn draw_reading(display: &mut DisplayBuffer, label: &str, value: &str, y: i32, color: QuadColor) {
+    let label_style = MonoTextStyle::new(&FONT_6X10, QuadColor::Black);
+    let value_style = MonoTextStyle::new(&FONT_10X20, color);
+
+    Text::new(label, Point::new(20, y), label_style)
+        .draw(display)
+        .unwrap();
+    Text::new(value, Point::new(90, y), value_style)
+        .draw(display)
+        .unwrap();
+}
@@ -0,0 +1,145 @@
+//! `DrawTarget` adapters that let other drawing code render into a
+//! [`DisplayBuffer`].
+
+use embedded_graphics::pixelcolor::Gray8;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+use crate::{DisplayBuffer, QuadColor};
+
+/// Luma cut-offs used to quantize grayscale onto the four inks.
+///
+/// A gray value below `black` becomes black, below `red` becomes red, below
+/// `yellow` becomes yellow and anything else white.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct GrayThresholds {
+    pub black: u8,
+    pub red: u8,
+    pub yellow: u8,
+}
+
+impl GrayThresholds {
+    /// Midpoints between the luma of the [`PANEL_INKS`](crate::PANEL_INKS).
+    pub const DEFAULT: Self = Self {
+        black: 51,
+        red: 128,
+        yellow: 201,
+    };
+
+    pub fn quantize(&self, luma: u8) -> QuadColor {
+        if luma < self.black {
+            QuadColor::Black
+        } else if luma < self.red {
+            QuadColor::Red
+        } else if luma < self.yellow {
+            QuadColor::Yellow
+        } else {
+            QuadColor::White
+        }
+    }
+}
+
+impl Default for GrayThresholds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A `Gray8` draw target over a [`DisplayBuffer`], so existing grayscale
+/// rendering code (e.g. antialiased font renderers) can draw to the panel
+/// unchanged.
+pub struct DrawTargetAdapter<'a> {
+    buffer: &'a mut DisplayBuffer,
+    thresholds: GrayThresholds,
+}
+
+impl<'a> DrawTargetAdapter<'a> {
+    pub fn new(buffer: &'a mut DisplayBuffer) -> Self {
+        Self::with_thresholds(buffer, GrayThresholds::DEFAULT)
+    }
+
+    pub fn with_thresholds(buffer: &'a mut DisplayBuffer, thresholds: GrayThresholds) -> Self {
+        Self { buffer, thresholds }
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: GrayThresholds) {
+        self.thresholds = thresholds;
+    }
+}
+
+impl DrawTarget for DrawTargetAdapter<'_> {
+    type Color = Gray8;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let thresholds = self.thresholds;
+        self.buffer.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, gray)| Pixel(point, thresholds.quantize(gray.luma()))),
+        )
+    }
+}
+
+impl OriginDimensions for DrawTargetAdapter<'_> {
+    fn size(&self) -> Size {
+        self.buffer.size()
+    }
+}
+
+/// A view of one region of a [`DisplayBuffer`], e.g. a status bar, that widget
+/// code can treat as a whole target of its own.
+///
+/// Coordinates are relative to the region's top-left corner and anything
+/// drawn outside the region is clipped, so the rest of the buffer can't be
+/// overdrawn.
+pub struct Clipped<'a> {
+    buffer: &'a mut DisplayBuffer,
+    area: Rectangle,
+}
+
+impl<'a> Clipped<'a> {
+    /// Clips drawing to `area`, itself clipped to the buffer.
+    pub fn new(buffer: &'a mut DisplayBuffer, area: Rectangle) -> Self {
+        let area = area.intersection(&buffer.bounding_box());
+        Self { buffer, area }
+    }
+
+    /// The region being drawn to, in buffer coordinates.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl DrawTarget for Clipped<'_> {
+    type Color = QuadColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        self.buffer.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point + area.top_left, color))
+                .filter(|Pixel(point, _)| area.contains(*point)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.translate(self.area.top_left).intersection(&self.area);
+        self.buffer.fill_rect(area, color);
+        Ok(())
+    }
+}
+
+impl OriginDimensions for Clipped<'_> {
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
@@ -0,0 +1,103 @@
+//! Indexed BMP loading for the quad-color panel (`bmp` feature).
+//!
+//! 24-bit BMPs are color matched through `QuadColor::from(Rgb888)` and carry
+//! three bytes per pixel. An indexed BMP whose palette was authored
+//! for the panel is far smaller and needs no color matching: each palette index
+//! is mapped straight to a [`QuadColor`] by a caller-supplied table.
+
+#[cfg(feature = "defmt")]
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+#[cfg(feature = "defmt")]
+use tinybmp::Bmp;
+use tinybmp::{Bpp, RawBmp};
+
+use crate::{DisplayBuffer, QuadColor};
+
+/// Errors returned by [`DisplayBuffer::draw_indexed_bmp`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum IndexedBmpError {
+    /// The image is not a 1, 4 or 8-bit palette BMP.
+    NotIndexed,
+    /// `palette_map` has fewer entries than the image's color table.
+    PaletteTooSmall { needed: usize, provided: usize },
+}
+
+/// Number of pixels mapped to each ink by [`DisplayBuffer::draw_bmp_traced`].
+#[cfg(feature = "defmt")]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, defmt::Format)]
+pub struct ColorHistogram {
+    pub black: u32,
+    pub white: u32,
+    pub red: u32,
+    pub yellow: u32,
+}
+
+impl DisplayBuffer {
+    /// Draws an indexed BMP with its top-left corner at `origin`.
+    ///
+    /// Palette index `i` in the file is drawn as `palette_map[i]`, so the
+    /// palette's RGB values are ignored and only its order matters. For example,
+    /// a 4-bit file exported with the palette black, white, red, yellow is drawn
+    /// with `&[QuadColor::Black, QuadColor::White, QuadColor::Red,
+    /// QuadColor::Yellow]`. `palette_map` must cover every color table entry;
+    /// stray indices beyond the color table are drawn as white.
+    ///
+    /// BMP has no 2-bit format, so a 4-color asset is stored as 4-bit with
+    /// unused entries. Pixels falling outside the buffer are clipped.
+    pub fn draw_indexed_bmp(
+        &mut self,
+        bmp: &RawBmp<'_>,
+        origin: Point,
+        palette_map: &[QuadColor],
+    ) -> Result<(), IndexedBmpError> {
+        if !matches!(bmp.header().bpp, Bpp::Bits1 | Bpp::Bits4 | Bpp::Bits8) {
+            return Err(IndexedBmpError::NotIndexed);
+        }
+        let needed = bmp.color_table().map_or(0, |table| table.len());
+        if palette_map.len() < needed {
+            return Err(IndexedBmpError::PaletteTooSmall {
+                needed,
+                provided: palette_map.len(),
+            });
+        }
+
+        let pixels = bmp.pixels().map(|pixel| {
+            let color = palette_map
+                .get(pixel.color as usize)
+                .copied()
+                .unwrap_or(QuadColor::White);
+            Pixel(origin + pixel.position, color)
+        });
+        let _ = self.draw_iter(pixels);
+
+        Ok(())
+    }
+
+    /// Draws an RGB BMP with its top-left corner at `origin`, mapping each
+    /// pixel with `QuadColor::from(Rgb888)`, and logs via defmt how many pixels
+    /// went to each ink.
+    ///
+    /// Meant for debugging images that come out wrong: a histogram like "90 %
+    /// white" points straight at a source image or threshold that doesn't suit
+    /// the nearest-color mapping. Only available with the `defmt` feature, so
+    /// normal drawing pays nothing for it.
+    #[cfg(feature = "defmt")]
+    pub fn draw_bmp_traced(&mut self, bmp: &Bmp<'_, Rgb888>, origin: Point) -> ColorHistogram {
+        let mut histogram = ColorHistogram::default();
+        let pixels = bmp.pixels().map(|Pixel(point, rgb)| {
+            let color = QuadColor::from(rgb);
+            match color {
+                QuadColor::Black => histogram.black += 1,
+                QuadColor::White => histogram.white += 1,
+                QuadColor::Red => histogram.red += 1,
+                QuadColor::Yellow => histogram.yellow += 1,
+            }
+            Pixel(origin + point, color)
+        });
+        let _ = self.draw_iter(pixels);
+
+        defmt::info!("BMP color mapping: {}", histogram);
+        histogram
+    }
+}
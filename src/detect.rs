@@ -0,0 +1,57 @@
+//! Identifying the attached panel from ID strap pins.
+//!
+//! Adafruit's schematics for the ThinkInk FPC connector and EYESPI breakout
+//! don't publish a pin-to-model table, so none is built in here. If your
+//! carrier board or panel adapter straps GPIOs to identify the panel, describe
+//! that strapping with a table of `(id, model)` pairs and pass it to
+//! [`detect_panel`]:
+//!
+//! ```ignore
+//! // ID0 low, ID1 high on our adapter for the 2.13" quad-color panel.
+//! const PANELS: &[(u8, PanelModel)] = &[(0b10, PanelModel::Quad213)];
+//! let model = detect_panel(&mut [id0, id1], PANELS)?;
+//! ```
+
+use embedded_hal::digital::InputPin;
+
+/// JD79661 panels this driver can drive.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum PanelModel {
+    /// 2.13" 250x122 black/white/red/yellow panel.
+    Quad213,
+}
+
+impl PanelModel {
+    /// Visible resolution as `(width, height)` in display orientation.
+    pub const fn size(self) -> (usize, usize) {
+        match self {
+            PanelModel::Quad213 => (crate::WIDTH, crate::HEIGHT),
+        }
+    }
+}
+
+/// Reads the ID pins as a number, with `id_pins[0]` as bit 0 and a high pin
+/// as a 1. At most 8 pins are read.
+pub fn read_panel_id<P: InputPin>(id_pins: &mut [P]) -> Result<u8, P::Error> {
+    let mut id = 0;
+    for (bit, pin) in id_pins.iter_mut().take(8).enumerate() {
+        if pin.is_high()? {
+            id |= 1 << bit;
+        }
+    }
+    Ok(id)
+}
+
+/// Reads the ID pins and looks the result up in `table`. Returns `None` if the
+/// ID isn't listed, e.g. because no panel is attached.
+pub fn detect_panel<P: InputPin>(
+    id_pins: &mut [P],
+    table: &[(u8, PanelModel)],
+) -> Result<Option<PanelModel>, P::Error> {
+    let id = read_panel_id(id_pins)?;
+    Ok(table
+        .iter()
+        .find(|(entry, _)| *entry == id)
+        .map(|&(_, model)| model))
+}
@@ -0,0 +1,46 @@
+//! Frame dumps for inspecting the exact panel image on a host.
+
+use crate::{pack_frame_chunk, DisplayBuffer, HEIGHT, RAM_BYTES, RAM_GATES, RAM_ROW_BYTES, WIDTH};
+
+/// First bytes of every [`DisplayBuffer::dump_frame`] dump.
+pub const FRAME_DUMP_MAGIC: [u8; 4] = *b"JD79";
+
+/// Size of the header in front of the frame in a dump.
+pub const FRAME_DUMP_HEADER_LEN: usize = 12;
+
+impl DisplayBuffer {
+    /// Streams the frame [`Jd79661::update_frames`](crate::Jd79661::update_frames)
+    /// would send, behind a small header, through `write`, e.g. to a USB serial
+    /// port so a desktop tool can reconstruct the image without a camera.
+    ///
+    /// The dump is [`FRAME_DUMP_HEADER_LEN`] + [`RAM_BYTES`] bytes:
+    ///
+    /// | offset | size | content                                    |
+    /// |--------|------|--------------------------------------------|
+    /// | 0      | 4    | [`FRAME_DUMP_MAGIC`], `b"JD79"`            |
+    /// | 4      | 2    | display width (250), little-endian         |
+    /// | 6      | 2    | display height (122), little-endian        |
+    /// | 8      | 4    | frame length in bytes (8000), little-endian |
+    /// | 12     | 8000 | packed frame                               |
+    ///
+    /// The frame is in panel RAM order: 250 gate lines of 32 bytes, one per
+    /// display column x, each byte holding four 2-bit codes
+    /// ([`QuadColor::to_u8`](crate::QuadColor::to_u8)) MSB first. Code `n`
+    /// of a line is the pixel at `y = 121 - n`; codes 122..128 are padding.
+    /// `write` gets the header first and then one gate line per call.
+    pub fn dump_frame<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        let mut header = [0u8; FRAME_DUMP_HEADER_LEN];
+        header[..4].copy_from_slice(&FRAME_DUMP_MAGIC);
+        header[4..6].copy_from_slice(&(WIDTH as u16).to_le_bytes());
+        header[6..8].copy_from_slice(&(HEIGHT as u16).to_le_bytes());
+        header[8..].copy_from_slice(&(RAM_BYTES as u32).to_le_bytes());
+        write(&header)?;
+
+        let mut line = [0u8; RAM_ROW_BYTES];
+        for gate in 0..RAM_GATES {
+            pack_frame_chunk(self, gate..gate + 1, &mut line);
+            write(&line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Host-side frame packing (`host` feature), e.g. for a `build.rs` that turns
+//! assets into frames ready for [`Jd79661::write_raw_frame`](crate::Jd79661::write_raw_frame).
+//!
+//! This is for host tools and `build.rs` only. It uses `alloc`, which the
+//! firmware has no allocator for, so it is compiled out on bare-metal targets
+//! (`target_os = "none"`) and `--all-features` device builds keep linking.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+
+use crate::{pack_frame_chunk, DisplayBuffer, QuadColor, RAM_BYTES, RAM_GATES};
+
+/// Maps an RGB image onto the four inks and packs it exactly as
+/// [`Jd79661::update_frames`](crate::Jd79661::update_frames) would, returning [`RAM_BYTES`] bytes.
+///
+/// `rgb` holds `width * height` pixels as `r, g, b` bytes, row by row from the
+/// top-left. Each pixel becomes the nearest ink via `QuadColor::from(Rgb888)`.
+/// The image is drawn at the display origin; parts beyond the 250x122 panel
+/// are cropped and uncovered pixels stay white.
+///
+/// # Panics
+///
+/// If `rgb` is shorter than `width * height * 3` bytes.
+pub fn pack_rgb_image(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    assert!(rgb.len() >= width * height * 3, "rgb image too short");
+
+    let mut display = DisplayBuffer::new();
+    let pixels = rgb
+        .chunks_exact(3)
+        .take(width * height)
+        .enumerate()
+        .map(|(i, px)| {
+            let point = Point::new((i % width) as i32, (i / width) as i32);
+            Pixel(point, QuadColor::from(Rgb888::new(px[0], px[1], px[2])))
+        });
+    let _ = display.draw_iter(pixels);
+
+    let mut frame = vec![0; RAM_BYTES];
+    pack_frame_chunk(&display, 0..RAM_GATES, &mut frame);
+    frame
+}
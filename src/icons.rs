@@ -0,0 +1,72 @@
+//! Common status glyphs (`icons` feature).
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+
+use crate::{DisplayBuffer, QuadColor};
+
+/// Size of the [`DisplayBuffer::draw_wifi`] glyph.
+pub const WIFI_SIZE: Size = Size::new(15, 12);
+/// Size of the [`DisplayBuffer::draw_bluetooth`] glyph.
+pub const BLUETOOTH_SIZE: Size = Size::new(7, 11);
+
+impl DisplayBuffer {
+    /// Draws a battery filling `rect`, with the terminal nub on the right and
+    /// the body filled from the left in proportion to `fraction` (clamped to
+    /// `0.0..=1.0`).
+    ///
+    /// The nub takes the rightmost 2 px and half the height, the body is a
+    /// [`draw_progress_bar`](Self::draw_progress_bar) over the rest.
+    pub fn draw_battery(&mut self, rect: Rectangle, fraction: f32, color: QuadColor) {
+        let Size { width, height } = rect.size;
+        if width < 3 || height == 0 {
+            return;
+        }
+        let body = Rectangle::new(rect.top_left, Size::new(width - 2, height));
+        self.draw_progress_bar(body, fraction, color, color);
+
+        let nub_height = (height / 2).max(1);
+        self.fill_rect(
+            Rectangle::new(
+                rect.top_left + Point::new((width - 2) as i32, ((height - nub_height) / 2) as i32),
+                Size::new(2, nub_height),
+            ),
+            color,
+        );
+    }
+
+    /// Draws a signal-strength glyph of four bars rising to the right, with
+    /// its top-left corner at `origin` (see [`WIFI_SIZE`]). The first `bars`
+    /// bars (clamped to 4) are solid; the rest are shown as a 1 px stub.
+    pub fn draw_wifi(&mut self, origin: Point, bars: u8, color: QuadColor) {
+        let bottom = origin.y + WIFI_SIZE.height as i32;
+        for bar in 0..4u32 {
+            let height = if bar < bars as u32 { 3 * (bar + 1) } else { 1 };
+            self.fill_rect(
+                Rectangle::new(
+                    Point::new(origin.x + 4 * bar as i32, bottom - height as i32),
+                    Size::new(3, height),
+                ),
+                color,
+            );
+        }
+    }
+
+    /// Draws the Bluetooth rune with its top-left corner at `origin` (see
+    /// [`BLUETOOTH_SIZE`]).
+    pub fn draw_bluetooth(&mut self, origin: Point, color: QuadColor) {
+        let style = PrimitiveStyle::with_stroke(color, 1);
+        let strokes = [
+            ((3, 0), (3, 10)),
+            ((3, 0), (6, 3)),
+            ((6, 3), (0, 8)),
+            ((3, 10), (6, 7)),
+            ((6, 7), (0, 2)),
+        ];
+        for ((x0, y0), (x1, y1)) in strokes {
+            let _ = Line::new(origin + Point::new(x0, y0), origin + Point::new(x1, y1))
+                .into_styled(style)
+                .draw(self);
+        }
+    }
+}
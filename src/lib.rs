@@ -1,215 +1,2535 @@
 //! Shared driver code for JD79661 e-paper displays.
 #![no_std]
 
+#[cfg(all(feature = "host", not(target_os = "none")))]
+extern crate alloc;
+
+use core::convert::Infallible;
+use core::ops::Range;
+
+use embedded_graphics::pixelcolor::{Bgr565, Rgb565, Rgb888};
 use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
-use embedded_hal::spi::SpiDevice;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+mod adapter;
+#[cfg(feature = "bmp")]
+mod bmp;
+mod detect;
+mod dump;
+#[cfg(all(feature = "host", not(target_os = "none")))]
+mod host;
+#[cfg(feature = "icons")]
+pub mod icons;
+mod limiter;
+mod nopin;
+mod owned;
+mod palette;
+#[cfg(feature = "qr")]
+pub mod qr;
+mod recorder;
+mod rows;
+mod text;
+#[cfg(feature = "textbox")]
+pub mod textbox;
+mod tiled;
+mod widgets;
+
+pub use adapter::{Clipped, DrawTargetAdapter, GrayThresholds};
+#[cfg(all(feature = "bmp", feature = "defmt"))]
+pub use bmp::ColorHistogram;
+#[cfg(feature = "bmp")]
+pub use bmp::IndexedBmpError;
+pub use detect::{detect_panel, read_panel_id, PanelModel};
+pub use dump::{FRAME_DUMP_HEADER_LEN, FRAME_DUMP_MAGIC};
+#[cfg(all(feature = "host", not(target_os = "none")))]
+pub use host::pack_rgb_image;
+pub use limiter::{RefreshLimiter, DEFAULT_MIN_REFRESH_INTERVAL_MS};
+pub use nopin::NoPin;
+pub use owned::Jd79661Owned;
+pub use palette::Palette;
+pub use recorder::{DrawRecorder, RecorderFull};
+pub use rows::{RowView, ROW_PACKED_BYTES, ROW_PLANE_BYTES};
+pub use tiled::TiledDisplay;
+pub use widgets::TextDirection;
+
+/// How long init waits on BUSY by default before treating the panel as stuck,
+/// see [`Builder::busy_timeouts`].
+pub const DEFAULT_INIT_BUSY_TIMEOUT_MS: u32 = 5_000;
+/// How long a refresh may keep BUSY asserted by default. A full four-color
+/// refresh takes around 20 s, so this leaves generous headroom.
+pub const DEFAULT_REFRESH_BUSY_TIMEOUT_MS: u32 = 40_000;
+
+/// Errors returned by the [`Jd79661`] driver.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Jd79661Error<SPI> {
+    /// An SPI transfer failed.
+    Spi(SPI),
+    /// Driving CS, DC or RST, or reading BUSY, failed.
+    ///
+    /// Only the [`ErrorKind`](embedded_hal::digital::ErrorKind) is kept rather
+    /// than the pin's own error. The four pins each have their own error type
+    /// (e.g. RST on an I/O expander next to rp2040 GPIOs), and carrying them
+    /// all would add four type parameters to every driver error; the kind is
+    /// what `embedded-hal` defines as the portable part of a pin error.
+    Pin(embedded_hal::digital::ErrorKind),
+    /// The panel stayed busy during init, even after an automatic hardware
+    /// reset and second attempt.
+    InitFailed,
+    /// The panel was still busy when a wait timed out, so the operation was
+    /// aborted rather than sending commands mid-refresh.
+    BusyTimeout,
+    /// A refresh was requested while the panel's charge pumps were off; call
+    /// [`Jd79661::power_on`] first.
+    NotPowered,
+    /// A frame write was refused because a refresh is still running, see
+    /// [`Jd79661::try_update_frames`].
+    RefreshInProgress,
+    /// [`Jd79661::refresh_done`] can't tell when a refresh ends because the
+    /// panel was set up without a BUSY line, see [`Jd79661::new_no_busy`].
+    NoBusyLine,
+    /// [`Jd79661::test_connectivity`] saw no reaction from the panel.
+    NotResponding,
+    /// A raw frame didn't contain exactly the number of bytes the RAM holds.
+    FrameLength { expected: usize, actual: usize },
+    /// [`Jd79661::set_resolution`] was given a size that doesn't fit the
+    /// controller RAM.
+    InvalidResolution { width: u16, height: u16 },
+    /// [`Jd79661::display_frame_limited`] was called before the
+    /// [`RefreshLimiter`] interval had passed; retry in `remaining_ms`.
+    TooSoon { remaining_ms: u32 },
+    /// BUSY stayed asserted for `power_on_ms` after POWER ON during init,
+    /// outside the [`Builder::power_on_time_range`].
+    UnexpectedPanel { power_on_ms: u32 },
+}
+
+impl<SPI> Jd79661Error<SPI> {
+    /// The underlying SPI error, if this is a bus failure (which is usually
+    /// worth a retry) rather than a panel or usage problem.
+    pub fn as_spi(&self) -> Option<&SPI> {
+        match self {
+            Jd79661Error::Spi(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// The underlying GPIO error, if a control pin failed, which usually
+    /// points at wiring or pin configuration.
+    pub fn as_pin(&self) -> Option<embedded_hal::digital::ErrorKind> {
+        match self {
+            Jd79661Error::Pin(kind) => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+impl<SPI> From<SPI> for Jd79661Error<SPI> {
+    fn from(err: SPI) -> Self {
+        Jd79661Error::Spi(err)
+    }
+}
+
+/// Wraps a GPIO failure, keeping only its kind.
+fn pin_error<SPI>(err: impl embedded_hal::digital::Error) -> Jd79661Error<SPI> {
+    Jd79661Error::Pin(err.kind())
+}
+
+/// Init-time settings for [`Jd79661`].
+///
+/// Start from [`Builder::new`] (the same defaults [`Jd79661::new`] uses),
+/// adjust what your panel needs, then call [`Builder::init`].
+///
+/// There is deliberately no setting for a custom waveform LUT. The panel
+/// setting used at init selects the waveform stored in the controller's OTP,
+/// the JD79661's LUT register layout isn't publicly documented, and with the
+/// panel wired write-only the OTP tables can't be read back to serve as a
+/// starting point. Timing-related tuning is limited to
+/// [`booster`](Builder::booster) and [`Jd79661::set_data_interval`].
+#[derive(Copy, Clone, Debug)]
+pub struct Builder {
+    config: Config,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Config {
+    reset_low_ms: u32,
+    reset_high_ms: u32,
+    full_refresh_every: u32,
+    magic_key: u8,
+    init_clear: bool,
+    booster: [u8; 7],
+    diff_full_fraction: f32,
+    spi_hz: Option<u32>,
+    power_on_range_ms: Option<(u32, u32)>,
+    cs_delay_ns: u32,
+    init_busy_timeout_ms: u32,
+    refresh_busy_timeout_ms: u32,
+}
+
+impl Builder {
+    pub const fn new() -> Self {
+        Self {
+            config: Config {
+                reset_low_ms: 10,
+                reset_high_ms: 10,
+                full_refresh_every: 10,
+                magic_key: 0x78,
+                init_clear: false,
+                booster: [0x05, 0x00, 0x3F, 0x0A, 0x25, 0x12, 0x1A],
+                diff_full_fraction: 0.5,
+                spi_hz: None,
+                power_on_range_ms: None,
+                cs_delay_ns: 0,
+                init_busy_timeout_ms: DEFAULT_INIT_BUSY_TIMEOUT_MS,
+                refresh_busy_timeout_ms: DEFAULT_REFRESH_BUSY_TIMEOUT_MS,
+            },
+        }
+    }
+
+    /// Sets how long RST is held low, and how long to wait after releasing it
+    /// before talking to the controller.
+    ///
+    /// The 10 ms / 10 ms defaults match Adafruit's driver and the minimums
+    /// recommended for this controller family. Slow-to-settle panels that
+    /// occasionally fail init after a cold boot usually come up reliably with
+    /// 20 ms for both.
+    pub const fn reset_pulse(mut self, low_ms: u32, high_ms: u32) -> Self {
+        self.config.reset_low_ms = low_ms;
+        self.config.reset_high_ms = high_ms;
+        self
+    }
+
+    /// Sets only the settle time after RST is released, keeping the pulse
+    /// width; the second half of [`reset_pulse`](Self::reset_pulse).
+    ///
+    /// The controller ignores commands until its internal reset finishes, so
+    /// this wait comes before the first BUSY poll. The controller family asks
+    /// for at least 10 ms (the default); panels that intermittently fail init
+    /// with a clean reset pulse typically need 50 ms or more here.
+    pub const fn reset_settle(mut self, ms: u32) -> Self {
+        self.config.reset_high_ms = ms;
+        self
+    }
+
+    /// Makes [`Jd79661::update_partial`] do a full refresh instead once this
+    /// many partial refreshes have happened since the last full one. Defaults
+    /// to 10; 0 disables it.
+    ///
+    /// Partial refreshes skip the full waveform that fully drives every pixel,
+    /// so each one leaves a little residual charge (ghosting). That builds up,
+    /// and left for long it can image-retain into the panel permanently; a
+    /// periodic full refresh clears it.
+    pub const fn full_refresh_every(mut self, partials: u32) -> Self {
+        self.config.full_refresh_every = partials;
+        self
+    }
+
+    /// Sets the byte written to register 0x4D at the start of init. Defaults
+    /// to 0x78.
+    ///
+    /// The register is undocumented; 0x78 is copied from the init sequence in
+    /// Adafruit's JD79661 drivers (Arduino and CircuitPython), which came from
+    /// the panel vendor's sample code. Other panel lots may ship with a
+    /// different value in their vendor init code, and a wrong key leaves the
+    /// panel unresponsive.
+    pub const fn magic_key(mut self, key: u8) -> Self {
+        self.config.magic_key = key;
+        self
+    }
+
+    /// When set, init also writes an all-white frame to the panel RAM, so the
+    /// first [`Jd79661::display_frame`] can't briefly show whatever the RAM
+    /// held before the reset. Defaults to off, which saves the transfer when the
+    /// first thing you do is send a full frame anyway.
+    pub const fn init_clear(mut self, clear: bool) -> Self {
+        self.config.init_clear = clear;
+        self
+    }
+
+    /// Sets the 7 bytes sent to the Booster Soft Start register (0x06).
+    /// Defaults to Adafruit's `[0x05, 0x00, 0x3F, 0x0A, 0x25, 0x12, 0x1A]`.
+    ///
+    /// A full JD79661 datasheet isn't public, so this is based on the related
+    /// UC81xx/JD79xxx controllers, where the booster runs in phases and each
+    /// phase byte packs:
+    ///
+    /// | bits | field                                      |
+    /// |------|--------------------------------------------|
+    /// | 7..6 | soft-start period (longer = gentler)       |
+    /// | 5..3 | driving strength (lower = weaker, quieter) |
+    /// | 2..0 | minimum off time of the switching FET      |
+    ///
+    /// The remaining bytes fine-tune the phase timings. A gentler ramp reduces
+    /// audible coil noise and inrush on weak supplies, at the cost of a slower
+    /// start of each refresh; change one byte at a time and check the image
+    /// still fully develops.
+    pub const fn booster(mut self, soft_start: [u8; 7]) -> Self {
+        self.config.booster = soft_start;
+        self
+    }
+
+    /// Makes [`Jd79661::update_diff`] do a full refresh instead when the
+    /// changed area covers more than this fraction of the screen. Defaults to
+    /// 0.5.
+    ///
+    /// Past that point a partial refresh saves little time while still adding
+    /// ghosting, so the full waveform is the better trade.
+    pub const fn diff_full_fraction(mut self, fraction: f32) -> Self {
+        self.config.diff_full_fraction = fraction;
+        self
+    }
+
+    /// Tells the driver the SPI clock the bus was set up with, e.g. the
+    /// `4_000_000` passed to `Spi::init`, for
+    /// [`Jd79661::transfer_time_estimate`].
+    ///
+    /// This is only a hint: the driver has no way to read the real clock, and
+    /// nothing breaks if it is wrong or missing.
+    pub const fn spi_frequency_hint(mut self, hz: u32) -> Self {
+        self.config.spi_hz = Some(hz);
+        self
+    }
+
+    /// Waits `ns` nanoseconds after CS and DC change before each SPI write,
+    /// for control lines behind a level shifter whose propagation delay
+    /// otherwise violates the controller's setup time at higher SPI clocks.
+    ///
+    /// The default of 0 keeps the writes back to back. The wait runs as an
+    /// `Operation::DelayNs` inside the write's `SpiDevice` transaction, so no
+    /// extra delay provider is needed.
+    pub const fn cs_delay(mut self, ns: u32) -> Self {
+        self.config.cs_delay_ns = ns;
+        self
+    }
+
+    /// Sets how long BUSY may stay asserted before the driver gives up with
+    /// [`Jd79661Error::BusyTimeout`]: `init_ms` for init and power commands,
+    /// `refresh_ms` for refreshes.
+    ///
+    /// The defaults are [`DEFAULT_INIT_BUSY_TIMEOUT_MS`] and
+    /// [`DEFAULT_REFRESH_BUSY_TIMEOUT_MS`]. Lower the refresh timeout to catch
+    /// a disconnected BUSY line sooner; raise it for panels that refresh
+    /// slowly in the cold.
+    pub const fn busy_timeouts(mut self, init_ms: u32, refresh_ms: u32) -> Self {
+        self.config.init_busy_timeout_ms = init_ms;
+        self.config.refresh_busy_timeout_ms = refresh_ms;
+        self
+    }
+
+    /// Checks at init that BUSY stays asserted for `min_ms..=max_ms` after
+    /// POWER ON, failing with [`Jd79661Error::UnexpectedPanel`] otherwise.
+    ///
+    /// The panel ID can't be read back on this board, so this timing
+    /// fingerprint is the next best way to catch a mis-seated FPC or a
+    /// different panel early: BUSY that never asserts (0 ms) or charge pumps
+    /// that take far longer than usual point to wiring or the wrong panel.
+    /// The time depends on the panel and temperature, so the check is off
+    /// by default; with the `defmt` feature the measured time is logged at
+    /// every init, to pick a range around it.
+    pub const fn power_on_time_range(mut self, min_ms: u32, max_ms: u32) -> Self {
+        self.config.power_on_range_ms = Some((min_ms, max_ms));
+        self
+    }
+
+    /// Creates the driver without touching the hardware. The panel is reset and
+    /// initialized by the first [`Jd79661::ensure_initialized`] call.
+    pub fn build<CS, BUSY, DC, RST>(
+        self,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+    ) -> Jd79661<CS, BUSY, DC, RST> {
+        Jd79661 {
+            cs,
+            busy,
+            dc,
+            rst,
+            config: self.config,
+            initialized: false,
+            power: PowerState::Off,
+            partials_since_full: 0,
+            cdi: CDI_DEFAULT,
+            frame_crc: None,
+            last_full_ms: None,
+            last_partial_ms: None,
+            ram_gates: RAM_GATES,
+            ram_sources: RAM_SOURCES,
+            #[cfg(feature = "command-log")]
+            command_log: heapless::HistoryBuffer::new(),
+            busy_wired: true,
+        }
+    }
+
+    /// Resets and initializes the panel with these settings.
+    ///
+    /// If BUSY gets stuck during init (typically a first power-on glitch), the
+    /// panel is hardware reset and initialized once more before giving up with
+    /// [`Jd79661Error::InitFailed`].
+    pub fn init<SPI, DELAY, CS, BUSY, DC, RST>(
+        self,
+        spi: &mut SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Jd79661<CS, BUSY, DC, RST>, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+        CS: OutputPin,
+        BUSY: InputPin,
+        DC: OutputPin,
+        RST: OutputPin + StatefulOutputPin,
+    {
+        let mut driver = self.build(cs, busy, dc, rst);
+        driver.ensure_initialized(spi, delay)?;
+        Ok(driver)
+    }
+
+    /// Like [`init`](Self::init), but leaves the panel powered off, e.g. to
+    /// load the first frame into RAM before the charge pumps start. Call
+    /// [`Jd79661::power_on`] before refreshing.
+    pub fn init_unpowered<SPI, DELAY, CS, BUSY, DC, RST>(
+        self,
+        spi: &mut SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Jd79661<CS, BUSY, DC, RST>, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+        CS: OutputPin,
+        BUSY: InputPin,
+        DC: OutputPin,
+        RST: OutputPin + StatefulOutputPin,
+    {
+        let mut driver = self.build(cs, busy, dc, rst);
+        driver.reset_and_init(spi, delay, false)?;
+        Ok(driver)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// JD79661 driver implementation
+///
+/// The panel is wired write-only on this board (no MISO line), so nothing can
+/// be read back from the controller. In particular its on-chip temperature
+/// sensor, which it uses internally to pick a waveform, isn't readable; use a
+/// separate sensor such as the BME280 for ambient temperature.
 pub struct Jd79661<CS, BUSY, DC, RST> {
     cs: CS,
     busy: BUSY,
     dc: DC,
-    _rst: RST,
+    rst: RST,
+    config: Config,
+    initialized: bool,
+    power: PowerState,
+    partials_since_full: u32,
+    cdi: u8,
+    /// CRC-32 of the frame last sent by `update_frames`, while the panel RAM
+    /// is known to still hold it.
+    frame_crc: Option<u32>,
+    last_full_ms: Option<u32>,
+    last_partial_ms: Option<u32>,
+    /// Resolution programmed into the controller, see `set_resolution`.
+    ram_gates: usize,
+    ram_sources: usize,
+    #[cfg(feature = "command-log")]
+    command_log: heapless::HistoryBuffer<(u8, usize), COMMAND_LOG_LEN>,
+    /// Whether BUSY can be polled; false waits fixed times instead.
+    busy_wired: bool,
+}
+
+/// Number of commands [`Jd79661::last_commands`] remembers.
+#[cfg(feature = "command-log")]
+pub const COMMAND_LOG_LEN: usize = 32;
+
+/// Kind of panel refresh.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RefreshMode {
+    /// The whole panel with the full waveform, as done by
+    /// [`Jd79661::display_frame`].
+    Full,
+    /// A window of the panel, as done by [`Jd79661::update_partial`].
+    Partial,
+}
+
+/// Whether the panel's charge pumps are running, see [`Jd79661::power_on`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PowerState {
+    /// Powered down (after reset, [`Jd79661::new_unpowered`] or
+    /// [`Jd79661::power_off`]). RAM can still be written, but not displayed.
+    Off,
+    /// Ready to refresh.
+    On,
+    /// In deep sleep after [`Jd79661::sleep`]; RAM is lost and the panel only
+    /// responds again after [`Jd79661::wake`].
+    DeepSleep,
+}
+
+impl<CS, BUSY, DC, RST> Jd79661<CS, BUSY, DC, RST>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    pub fn new<SPI, DELAY>(
+        spi: &mut SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+    {
+        Builder::new().init(spi, cs, busy, dc, rst, delay)
+    }
+
+    /// Resets and initializes the panel like [`new`](Self::new), but leaves it
+    /// powered off. See [`Builder::init_unpowered`].
+    pub fn new_unpowered<SPI, DELAY>(
+        spi: &mut SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+    {
+        Builder::new().init_unpowered(spi, cs, busy, dc, rst, delay)
+    }
+
+    /// Hardware resets and initializes the panel unless that already happened,
+    /// so library code can call it without knowing whether the panel is set up.
+    ///
+    /// See [`Builder::init`] for the automatic retry on a stuck BUSY line.
+    pub fn ensure_initialized<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.initialized {
+            return Ok(());
+        }
+        self.reset_and_init(spi, delay, true)
+    }
+
+    /// Hardware resets and initializes the panel, retrying once on a stuck
+    /// BUSY line.
+    fn reset_and_init<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+        power_on: bool,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.reset(delay)?;
+        match self.init_panel(spi, delay, power_on) {
+            Err(Jd79661Error::BusyTimeout) => {
+                #[cfg(feature = "defmt")]
+                defmt::warn!("JD79661 stuck busy during init, retrying after hardware reset");
+                self.reset(delay)?;
+                match self.init_panel(spi, delay, power_on) {
+                    Err(Jd79661Error::BusyTimeout) => return Err(Jd79661Error::InitFailed),
+                    result => result?,
+                }
+            }
+            result => result?,
+        }
+        if self.config.init_clear {
+            self.clear_ram(spi, QuadColor::White)?;
+        }
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Whether the panel has been initialized and not reset since.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        self.power
+    }
+
+    /// Starts the panel's charge pumps (POWER ON), which refreshes need.
+    /// [`new`](Self::new) already does this. Out of deep sleep this
+    /// [`wake`](Self::wake)s the panel instead.
+    pub fn power_on<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.power == PowerState::DeepSleep {
+            return self.wake(spi, delay);
+        }
+        self.power_on_timed(spi, delay)?;
+        Ok(())
+    }
+
+    /// [`power_on`](Self::power_on), returning how long BUSY stayed asserted.
+    fn power_on_timed<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<u32, Jd79661Error<SPI::Error>> {
+        self.command(spi, 0x04, &[])?;
+        let waited_ms = self.wait_busy_for(
+            delay,
+            self.config.init_busy_timeout_ms,
+            FIXED_INIT_WAIT_MS,
+            &mut || {},
+        )?;
+        self.power = PowerState::On;
+        Ok(waited_ms)
+    }
+
+    /// Quick health check that the panel is connected and responding.
+    ///
+    /// The JD79661 on this board is wired write-only (no MISO), so nothing can
+    /// be read back. Instead this toggles the charge pumps off and on again (or
+    /// on and off, if they were off) and checks that BUSY asserts in response,
+    /// which needs working SPI, DC/CS and BUSY lines. The image and RAM are
+    /// left alone and the power state is restored. Fails with
+    /// [`Jd79661Error::NotResponding`] if BUSY never reacted or never released.
+    pub fn test_connectivity<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let (first, second) = match self.power {
+            PowerState::On => (0x02, 0x04), // POWER OFF, POWER ON
+            PowerState::Off => (0x04, 0x02),
+            PowerState::DeepSleep => return Err(Jd79661Error::NotPowered),
+        };
+        let mut responded = false;
+        for cmd in [first, second] {
+            self.command(spi, cmd, &[])?;
+            responded |= self.busy_asserts(delay)?;
+            match self.wait_busy_for(
+                delay,
+                self.config.init_busy_timeout_ms,
+                FIXED_INIT_WAIT_MS,
+                &mut || {},
+            ) {
+                Err(Jd79661Error::BusyTimeout) => return Err(Jd79661Error::NotResponding),
+                result => result?,
+            };
+        }
+        if !responded {
+            return Err(Jd79661Error::NotResponding);
+        }
+        Ok(())
+    }
+
+    /// Whether BUSY goes low within 100 ms, sampled every 100 µs.
+    fn busy_asserts<E, DELAY: DelayNs>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<bool, Jd79661Error<E>> {
+        for _ in 0..1_000 {
+            if self.busy.is_low().map_err(pin_error)? {
+                return Ok(true);
+            }
+            delay.delay_us(100);
+        }
+        Ok(false)
+    }
+
+    /// Stops the charge pumps (POWER OFF). The image stays on the panel and
+    /// RAM keeps its contents; call [`power_on`](Self::power_on) before the
+    /// next refresh.
+    pub fn power_off<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.command(spi, 0x02, &[])?;
+        self.wait_busy_for(
+            delay,
+            self.config.init_busy_timeout_ms,
+            FIXED_INIT_WAIT_MS,
+            &mut || {},
+        )?;
+        self.power = PowerState::Off;
+        Ok(())
+    }
+
+    /// Puts the panel into deep sleep, its lowest power state, e.g. between
+    /// hourly refreshes on battery: POWER OFF, then DEEP SLEEP (0x07, 0xA5).
+    ///
+    /// The image stays on screen, but the controller loses its RAM and
+    /// ignores everything but a hardware reset, so call [`wake`](Self::wake)
+    /// before the next use. CS is released even if a transfer fails.
+    ///
+    /// `delay` times the BUSY wait after POWER OFF, which has to finish before
+    /// DEEP SLEEP is sent, with the [`Builder::busy_timeouts`] init timeout (or
+    /// the fixed wait on panels without BUSY); DEEP SLEEP itself needs none.
+    pub fn sleep<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.power_off(spi, delay)?;
+        self.command(spi, 0x07, &[0xA5])?; // Deep Sleep, check code
+        self.power = PowerState::DeepSleep;
+        self.initialized = false;
+        self.frame_crc = None;
+        Ok(())
+    }
+
+    /// Brings the panel back from [`sleep`](Self::sleep): a hardware reset
+    /// followed by the same init sequence as [`new`](Self::new), leaving the
+    /// charge pumps on. RAM starts out undefined, so write a frame before the
+    /// next refresh.
+    pub fn wake<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.reset_and_init(spi, delay, true)
+    }
+
+    /// Recovers a confused panel (e.g. after a glitch or ESD event) by re-running
+    /// SWRESET and the full register setup, leaving it powered on and ready for
+    /// [`update_frames`](Self::update_frames).
+    ///
+    /// Unlike the hardware reset done at init, this doesn't touch the RST line.
+    pub fn soft_reset<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.initialized = false;
+        match self.init_panel(spi, delay, true) {
+            Err(Jd79661Error::BusyTimeout) => return Err(Jd79661Error::InitFailed),
+            result => result?,
+        }
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Brings the panel back to a known-good state after a brownout or other
+    /// event that may have corrupted its registers and RAM: a hardware reset,
+    /// full init (with the usual retry) and a full refresh to white.
+    ///
+    /// This is the heavy-handed counterpart of [`soft_reset`](Self::soft_reset),
+    /// which keeps RST alone and leaves RAM and the shown image untouched.
+    pub fn recover<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.reset_and_init(spi, delay, true)?;
+        self.clear_display(spi, delay)
+    }
+
+    /// Cuts short a refresh begun with [`start_refresh`](Self::start_refresh)
+    /// (or running on any other path) and returns the panel to a
+    /// command-ready, powered-on state.
+    ///
+    /// The controller has no command to stop a refresh, so this does a
+    /// hardware reset and full re-init. The panel keeps whatever the
+    /// interrupted waveform left behind, typically a half-updated or washed
+    /// out image that the next full refresh clears. RAM should be considered
+    /// lost; write a new frame before refreshing again.
+    pub fn abort_refresh<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.reset_and_init(spi, delay, true)
+    }
+
+    fn reset<E, DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), Jd79661Error<E>> {
+        self.initialized = false;
+        self.power = PowerState::Off;
+        self.frame_crc = None;
+
+        // Hardware reset
+        self.rst.set_low().map_err(pin_error)?;
+        delay.delay_ms(self.config.reset_low_ms);
+        self.rst.set_high().map_err(pin_error)?;
+        delay.delay_ms(self.config.reset_high_ms);
+        Ok(())
+    }
+
+    /// Runs SWRESET and the register setup, then powers the panel on if
+    /// `power_on` is set. Fails with [`Jd79661Error::BusyTimeout`] if BUSY never
+    /// released along the way.
+    fn init_panel<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+        power_on: bool,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.power = PowerState::Off;
+        self.wait_busy_for(
+            delay,
+            self.config.init_busy_timeout_ms,
+            FIXED_INIT_WAIT_MS,
+            &mut || {},
+        )?;
+        self.command(spi, 0x01, &[])?; // SWRESET
+        self.wait_busy_for(
+            delay,
+            self.config.init_busy_timeout_ms,
+            FIXED_INIT_WAIT_MS,
+            &mut || {},
+        )?;
+
+        // Magic key, see Builder::magic_key
+        self.command(spi, 0x4D, &[self.config.magic_key])?;
+
+        // Panel Setting (128x250 resolution)
+        self.command(spi, 0x00, &[0x8F, 0x29])?;
+
+        // Power setting
+        self.command(spi, 0x01, &[0x07, 0x00])?;
+
+        // Power offset
+        self.command(spi, 0x03, &[0x10, 0x54, 0x44])?;
+
+        // Booster Soft Start
+        let booster = self.config.booster;
+        self.command(spi, 0x06, &booster)?;
+
+        // CDI
+        self.command(spi, 0x50, &[self.cdi])?;
+
+        // TCON
+        self.command(spi, 0x60, &[0x02, 0x02, 0x02])?;
+
+        // Resolution (128 x 250 unless overridden by set_resolution)
+        let [hres_hi, hres_lo] = (self.ram_sources as u16).to_be_bytes();
+        let [vres_hi, vres_lo] = (self.ram_gates as u16).to_be_bytes();
+        self.command(spi, 0x61, &[hres_hi, hres_lo, vres_hi, vres_lo])?;
+
+        // Additional config registers from Adafruit
+        self.command(spi, 0xE7, &[0x1C])?;
+        self.command(spi, 0xE3, &[0x22])?;
+        self.command(spi, 0xB4, &[0xD0])?;
+        self.command(spi, 0xB5, &[0x03])?;
+        self.command(spi, 0xE9, &[0x01])?;
+        self.command(spi, 0x30, &[0x08])?;
+
+        if power_on {
+            let power_on_ms = self.power_on_timed(spi, delay)?;
+            #[cfg(feature = "defmt")]
+            defmt::info!("JD79661 power on took {} ms", power_on_ms);
+            if let Some((min_ms, max_ms)) = self.config.power_on_range_ms {
+                if self.busy_wired && !(min_ms..=max_ms).contains(&power_on_ms) {
+                    return Err(Jd79661Error::UnexpectedPanel { power_on_ms });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The last [`COMMAND_LOG_LEN`] commands sent, oldest first, as `(command,
+    /// parameter byte count)` pairs (`command-log` feature).
+    ///
+    /// Dumped over serial after a failure, this shows whether init completed
+    /// or where the panel stalled. Frame data streamed to RAM after a 0x10
+    /// isn't counted, so RAM writes show up with a length of 0.
+    #[cfg(feature = "command-log")]
+    pub fn last_commands(&self) -> impl Iterator<Item = (u8, usize)> + '_ {
+        self.command_log.oldest_ordered().copied()
+    }
+
+    /// Catches sequencing bugs (`strict` feature, debug builds only): every
+    /// command is sent after the previous operation's BUSY wait, so BUSY still
+    /// being asserted here means the caller raced a running refresh or init.
+    #[cfg(feature = "strict")]
+    fn assert_ready<E>(&mut self, cmd: u8) -> Result<(), Jd79661Error<E>> {
+        debug_assert!(
+            !self.is_busy()?,
+            "JD79661 command {:#04x} sent while the panel is busy",
+            cmd
+        );
+        Ok(())
+    }
+
+    /// Writes `bytes`, after the [`Builder::cs_delay`] setup time if one is
+    /// set.
+    fn write_spi<SPI: SpiDevice>(&self, spi: &mut SPI, bytes: &[u8]) -> Result<(), SPI::Error> {
+        match self.config.cs_delay_ns {
+            0 => spi.write(bytes),
+            ns => spi.transaction(&mut [Operation::DelayNs(ns), Operation::Write(bytes)]),
+        }
+    }
+
+    fn command<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        cmd: u8,
+        data: &[u8],
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        #[cfg(feature = "strict")]
+        self.assert_ready(cmd)?;
+        self.command_unchecked(spi, cmd, data)
+    }
+
+    /// [`command`](Self::command) without the `strict` BUSY check, for the
+    /// few commands that are deliberately sent while the panel may be busy.
+    fn command_unchecked<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        cmd: u8,
+        data: &[u8],
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        #[cfg(feature = "command-log")]
+        self.command_log.write((cmd, data.len()));
+
+        // CS is released before any error is passed on, so a failed transfer
+        // never leaves the panel selected.
+        self.dc.set_low().map_err(pin_error)?;
+        self.cs.set_low().map_err(pin_error)?;
+        let result = self.write_spi(spi, &[cmd]);
+        self.end_transfer(result)?;
+
+        if !data.is_empty() {
+            self.begin_data()?;
+            let result = self.write_spi(spi, data);
+            self.end_transfer(result)?;
+        }
+        Ok(())
+    }
+
+    /// Selects the panel for a data phase: DC high, then CS low.
+    fn begin_data<E>(&mut self) -> Result<(), Jd79661Error<E>> {
+        self.dc.set_high().map_err(pin_error)?;
+        self.cs.set_low().map_err(pin_error)
+    }
+
+    /// Releases CS after a transfer, even if it failed, so the panel is never
+    /// left selected. A transfer error takes precedence over a pin error.
+    fn end_transfer<E>(&mut self, result: Result<(), E>) -> Result<(), Jd79661Error<E>> {
+        let released = self.cs.set_high().map_err(pin_error);
+        result?;
+        released
+    }
+
+    /// Waits for a refresh to finish, failing with
+    /// [`Jd79661Error::BusyTimeout`] if it takes implausibly long.
+    fn wait_busy<E, DELAY: DelayNs>(
+        &mut self,
+        delay: &mut DELAY,
+        fixed_ms: u32,
+        yield_now: &mut impl FnMut(),
+    ) -> Result<u32, Jd79661Error<E>> {
+        let timeout_ms = self.config.refresh_busy_timeout_ms;
+        self.wait_busy_for(delay, timeout_ms, fixed_ms, yield_now)
+    }
+
+    /// Waits for BUSY to release, giving up with [`Jd79661Error::BusyTimeout`]
+    /// after roughly `timeout_ms`. `yield_now` runs once per 1 ms poll.
+    /// Returns roughly how many milliseconds the panel stayed busy.
+    ///
+    /// Without a BUSY line (see [`Jd79661::new_no_busy`]) this waits
+    /// `fixed_ms` instead, the worst case for the operation.
+    fn wait_busy_for<E, DELAY: DelayNs>(
+        &mut self,
+        delay: &mut DELAY,
+        timeout_ms: u32,
+        fixed_ms: u32,
+        yield_now: &mut impl FnMut(),
+    ) -> Result<u32, Jd79661Error<E>> {
+        if !self.busy_wired {
+            for _ in 0..fixed_ms {
+                yield_now();
+                delay.delay_ms(1);
+            }
+            return Ok(fixed_ms);
+        }
+
+        // Based on adafruit_jd79661.py, busy_state=False
+        // This means it is BUSY when LOW.
+        let mut waited_ms = 0;
+        while self.busy.is_low().map_err(pin_error)? {
+            if waited_ms >= timeout_ms {
+                return Err(Jd79661Error::BusyTimeout);
+            }
+            yield_now();
+            delay.delay_ms(1);
+            waited_ms += 1;
+        }
+        Ok(waited_ms)
+    }
+
+    /// Packs `display` and writes it to the panel RAM, returning the number of
+    /// bytes sent, which is always [`RAM_BYTES`] on success (unless
+    /// [`set_resolution`](Self::set_resolution) shrank the frame); bus
+    /// telemetry can count it.
+    ///
+    /// The frame is packed into an [`RAM_BYTES`] (8 KB) stack buffer first and
+    /// sent with a single `spi.write`, i.e. one `SpiDevice` transaction, so
+    /// the device's own chip select stays asserted for the whole frame and
+    /// buses with a large per-transaction cost pay it once.
+    pub fn update_frames<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        let mut frame = [0u8; RAM_BYTES];
+        pack_frame_chunk(display, 0..RAM_GATES, &mut frame);
+        let crc = packed_crc(&frame);
+        self.write_packed(spi, &mut frame, crc)
+    }
+
+    /// Sends a frame packed by [`pack_frame_chunk`], whose CRC-32 is `crc`,
+    /// trimmed to the programmed resolution.
+    fn write_packed<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        frame: &mut [u8; RAM_BYTES],
+        crc: u32,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        self.warn_if_unpowered();
+
+        if !self.full_resolution() {
+            let len = self.compact_frame(frame);
+            self.write_ram(spi, &frame[..len])?;
+            return Ok(len);
+        }
+        self.write_ram(spi, frame)?;
+        self.frame_crc = Some(crc);
+        Ok(frame.len())
+    }
+
+    /// Moves the part of each gate line inside the programmed resolution to
+    /// the front of `frame`, returning the resulting frame length.
+    fn compact_frame(&self, frame: &mut [u8; RAM_BYTES]) -> usize {
+        let row_bytes = self.ram_sources / 4;
+        for gate in 0..self.ram_gates {
+            let start = gate * RAM_ROW_BYTES;
+            frame.copy_within(start..start + row_bytes, gate * row_bytes);
+        }
+        self.ram_gates * row_bytes
+    }
+
+    fn full_resolution(&self) -> bool {
+        (self.ram_gates, self.ram_sources) == (RAM_GATES, RAM_SOURCES)
+    }
+
+    /// Overrides the resolution register (0x61) at runtime, e.g. for a panel
+    /// identified through its FPC ID pins, and makes
+    /// [`update_frames`](Self::update_frames) send only that part of each
+    /// buffer: the first `width` gate lines (display columns from x = 0) and
+    /// the first `height` sources of each (display rows from the bottom).
+    ///
+    /// `width` must be 1..=[`RAM_GATES`] and `height` a multiple of 4 in
+    /// 4..=128, or this fails with [`Jd79661Error::InvalidResolution`]. Call
+    /// it before the next `update_frames`, or that frame lands misaligned. The
+    /// setting survives resets. [`write_raw_frame`](Self::write_raw_frame),
+    /// the streaming `update_from_*` methods and partial windows still address
+    /// the full 128 x 250 RAM.
+    pub fn set_resolution<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        width: u16,
+        height: u16,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let (gates, sources) = (width as usize, height as usize);
+        if !(1..=RAM_GATES).contains(&gates)
+            || !(4..=RAM_SOURCES).contains(&sources)
+            || sources % 4 != 0
+        {
+            return Err(Jd79661Error::InvalidResolution { width, height });
+        }
+        let [hres_hi, hres_lo] = height.to_be_bytes();
+        let [vres_hi, vres_lo] = width.to_be_bytes();
+        self.command(spi, 0x61, &[hres_hi, hres_lo, vres_hi, vres_lo])?;
+        self.ram_gates = gates;
+        self.ram_sources = sources;
+        self.frame_crc = None;
+        Ok(())
+    }
+
+    /// Like [`update_frames`](Self::update_frames), but sends the frame one
+    /// 32-byte gate line at a time and calls `on_row` after each with the
+    /// number of lines sent so far (1 to [`RAM_GATES`]).
+    ///
+    /// The callback runs 250 times per frame, which is fine-grained enough to
+    /// drive a progress indicator or feed a watchdog during the transfer. It
+    /// needs only a one-line buffer instead of the 8 KB one, at the cost of a
+    /// separate `spi.write` per line.
+    pub fn update_frames_with<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        mut on_row: impl FnMut(u16),
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        self.warn_if_unpowered();
+        self.frame_crc = None;
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+
+        let mut row = [0u8; RAM_ROW_BYTES];
+        let row_bytes = self.ram_sources / 4;
+        let mut crc = !0;
+        let mut result = Ok(());
+        for gate in 0..self.ram_gates {
+            pack_frame_chunk(display, gate..gate + 1, &mut row);
+            if let Err(err) = self.write_spi(spi, &row[..row_bytes]) {
+                result = Err(err);
+                break;
+            }
+            crc = row.iter().fold(crc, |crc, &byte| crc32_update(crc, byte));
+            on_row(gate as u16 + 1);
+        }
+        self.end_transfer(result)?;
+
+        if self.full_resolution() {
+            self.frame_crc = Some(!crc);
+        }
+        Ok(self.ram_gates * row_bytes)
+    }
+
+    /// Writes `frame` to the panel RAM in one transaction.
+    ///
+    /// Transfers split into several `spi.write` calls (as the streaming
+    /// `update_from_*` methods must) are separate `SpiDevice` transactions, and
+    /// the device may toggle its chip select between them. That is harmless
+    /// here only because the panel's CS is the driver's `cs` pin, held low for
+    /// the whole data phase; if the panel's CS were the `SpiDevice`'s, a chunked
+    /// transfer would have to go through one `SpiDevice::transaction` with an
+    /// `Operation::Write` per chunk to avoid mid-frame CS edges.
+    fn write_ram<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        frame: &[u8],
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.frame_crc = None;
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+        let result = self.write_spi(spi, frame);
+        self.end_transfer(result)
+    }
+
+    /// Whether the panel is busy, e.g. with a refresh started elsewhere.
+    ///
+    /// RAM writes while busy corrupt the frame being shown, so event-driven
+    /// code should check this, or use [`try_update_frames`](Self::try_update_frames)
+    /// or [`update_frames_when_ready`](Self::update_frames_when_ready). A
+    /// failed BUSY read is reported as [`Jd79661Error::Pin`] rather than
+    /// guessed at, since treating it as idle would let RAM writes race a
+    /// refresh.
+    pub fn is_busy<E>(&mut self) -> Result<bool, Jd79661Error<E>> {
+        self.busy.is_low().map_err(pin_error)
+    }
+
+    /// The literal level of the BUSY pin, for diagnostics and wiring checks.
+    ///
+    /// Unlike [`is_busy`](Self::is_busy), this doesn't interpret the level (the
+    /// JD79661 holds BUSY low while working).
+    pub fn busy_high<E>(&mut self) -> Result<bool, Jd79661Error<E>> {
+        self.busy.is_high().map_err(pin_error)
+    }
+
+    /// Like [`update_frames`](Self::update_frames), but fails with
+    /// [`Jd79661Error::RefreshInProgress`] instead of writing while the panel
+    /// is busy.
+    pub fn try_update_frames<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        if self.is_busy()? {
+            return Err(Jd79661Error::RefreshInProgress);
+        }
+        self.update_frames(spi, display)
+    }
+
+    /// Waits for any running refresh to finish, then does
+    /// [`update_frames`](Self::update_frames). Fails with
+    /// [`Jd79661Error::BusyTimeout`] if the panel never becomes ready.
+    pub fn update_frames_when_ready<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        delay: &mut DELAY,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        // Without BUSY there is nothing to wait for: the driver's own
+        // refreshes already block for their fixed time.
+        self.wait_busy(delay, 0, &mut || {})?;
+        self.update_frames(spi, display)
+    }
+
+    /// Like [`update_frames`](Self::update_frames), but skips the transfer and
+    /// returns `Ok(false)` when `display` packs to the same frame that the last
+    /// `update_frames` sent, so unchanged content costs neither the transfer nor
+    /// a refresh. `force` always sends.
+    ///
+    /// Frames are compared by the CRC-32 of their packed bytes, computed over
+    /// the same stack buffer that is then sent, so the check costs no extra
+    /// packing pass. Any other RAM write (raw frames, partial updates, a
+    /// reset) makes the next call send.
+    pub fn update_frames_if_changed<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        force: bool,
+    ) -> Result<bool, Jd79661Error<SPI::Error>> {
+        let mut frame = [0u8; RAM_BYTES];
+        pack_frame_chunk(display, 0..RAM_GATES, &mut frame);
+        let crc = packed_crc(&frame);
+        if !force && self.frame_crc == Some(crc) {
+            return Ok(false);
+        }
+        self.write_packed(spi, &mut frame, crc)?;
+        Ok(true)
+    }
+
+    /// CRC-32 of the frame the panel RAM holds, as sent by the last
+    /// [`update_frames`](Self::update_frames), or `None` before any frame or
+    /// after another kind of RAM write (see
+    /// [`update_frames_if_changed`](Self::update_frames_if_changed)).
+    ///
+    /// E-paper keeps its image without power, so persisting this value (e.g.
+    /// to flash) after each refresh lets the firmware skip redrawing after a
+    /// reboot: render the new content, and if its
+    /// [`DisplayBuffer::frame_crc`] equals the stored value, the panel already
+    /// shows it and neither the transfer nor the refresh is needed.
+    pub fn current_frame_crc(&self) -> Option<u32> {
+        self.frame_crc
+    }
+
+    /// Logs a warning when a frame is written while the panel is powered off.
+    /// That isn't an error, as RAM can be loaded before powering on, but a
+    /// forgotten [`power_on`](Self::power_on) otherwise shows up only as a
+    /// panel that never changes.
+    fn warn_if_unpowered(&self) {
+        #[cfg(feature = "defmt")]
+        if self.power != PowerState::On {
+            defmt::warn!(
+                "JD79661 frame written while powered off; call power_on before refreshing"
+            );
+        }
+    }
+
+    /// Writes an already packed frame of [`RAM_BYTES`] bytes (2 bits per pixel,
+    /// in the controller's RAM order) to the panel RAM.
+    pub fn write_raw_frame<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        frame: &[u8],
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if frame.len() != RAM_BYTES {
+            return Err(Jd79661Error::FrameLength {
+                expected: RAM_BYTES,
+                actual: frame.len(),
+            });
+        }
+        self.write_ram(spi, frame)?;
+        Ok(())
+    }
+
+    /// Streams a packed frame to the panel RAM, calling `reader` with each byte
+    /// offset from 0 to [`RAM_BYTES`]. Lets the frame come from flash or other
+    /// storage instead of a [`DisplayBuffer`] in RAM.
+    pub fn update_from_reader<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        mut reader: impl FnMut(usize) -> u8,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.frame_crc = None;
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+
+        let mut row = [0u8; RAM_ROW_BYTES];
+        let mut result = Ok(());
+        for offset in (0..RAM_BYTES).step_by(RAM_ROW_BYTES) {
+            for (i, byte) in row.iter_mut().enumerate() {
+                *byte = reader(offset + i);
+            }
+            result = self.write_spi(spi, &row);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.end_transfer(result)
+    }
+
+    /// Streams packed frame bytes to the panel RAM as the iterator yields them.
+    ///
+    /// Fails with [`Jd79661Error::FrameLength`] unless exactly [`RAM_BYTES`]
+    /// bytes are yielded. Bytes are sent as they arrive, so after that error the
+    /// RAM holds a partial frame that shouldn't be displayed.
+    pub fn update_from_iter<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        bytes: impl IntoIterator<Item = u8>,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.frame_crc = None;
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+
+        let mut bytes = bytes.into_iter();
+        let mut row = [0u8; RAM_ROW_BYTES];
+        let mut sent = 0;
+        let mut result = Ok(());
+        while sent < RAM_BYTES {
+            let mut len = 0;
+            for (slot, byte) in row.iter_mut().zip(bytes.by_ref()) {
+                *slot = byte;
+                len += 1;
+            }
+            if len == 0 {
+                break;
+            }
+            if let Err(err) = self.write_spi(spi, &row[..len]) {
+                result = Err(err);
+                break;
+            }
+            sent += len;
+        }
+        self.end_transfer(result)?;
+
+        let extra = bytes.count();
+        if sent != RAM_BYTES || extra != 0 {
+            return Err(Jd79661Error::FrameLength {
+                expected: RAM_BYTES,
+                actual: sent + extra,
+            });
+        }
+        Ok(())
+    }
+
+    /// Replaces one color plane of `display` with `data` and refreshes only the
+    /// area where that plane changed.
+    ///
+    /// The JD79661 has a single 2-bit-per-pixel RAM rather than one RAM per
+    /// color, so an accent plane can't be sent on its own. Instead the changed
+    /// region is re-sent from the updated `display` through a partial window,
+    /// which keeps the transfer and the refreshed area small for status
+    /// overlays. `QuadColor::Black` and `QuadColor::White` select the
+    /// black/white plane.
+    pub fn update_accent<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &mut DisplayBuffer,
+        plane: QuadColor,
+        data: &[u8; BUF_SIZE],
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        // Checked before `display` is touched: once the plane is copied in, a
+        // retry with the same `data` would find nothing changed and skip it.
+        if self.power != PowerState::On {
+            return Err(Jd79661Error::NotPowered);
+        }
+        let target = match plane {
+            QuadColor::Red => &mut display.red,
+            QuadColor::Yellow => &mut display.yellow,
+            QuadColor::Black | QuadColor::White => &mut display.bw,
+        };
+
+        let Some(window) = RamWindow::changed(target, data) else {
+            return Ok(());
+        };
+        target.copy_from_slice(data);
+
+        self.refresh_window(spi, display, &window, delay)
+    }
+
+    /// Sends the pixels of `display` inside `area` and refreshes only that part
+    /// of the panel.
+    ///
+    /// For an `x`, `y`, `w`, `h` window see
+    /// [`update_partial_xywh`](Self::update_partial_xywh).
+    ///
+    /// The window is rounded outward to the controller's 4-pixel RAM byte
+    /// boundaries, so a few pixels around `area` are re-sent too. The panel is
+    /// mounted rotated, with display columns on the gates and rows on the
+    /// sources, so only the vertical edges move: `x` and `w` are kept exactly,
+    /// while `y` and `h` grow to whole 4-row groups (counted from the bottom
+    /// edge, as the sources run upwards). Every
+    /// [`Builder::full_refresh_every`] partials this does a full
+    /// [`update_frames`](Self::update_frames) + [`display_frame`](Self::display_frame)
+    /// instead, to clear accumulated ghosting.
+    pub fn update_partial<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        area: Rectangle,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let area = area.intersection(&display.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let window = RamWindow::covering(
+            area.top_left.x as usize,
+            area.top_left.y as usize,
+            bottom_right.x as usize + 1,
+            bottom_right.y as usize + 1,
+        );
+        self.refresh_window(spi, display, &window, delay)
+    }
+
+    /// [`update_partial`](Self::update_partial) for the window `w` pixels wide
+    /// and `h` high with its top-left corner at (`x`, `y`).
+    ///
+    /// The RAM packs 4 pixels per byte, and the window is rounded outward to
+    /// those byte boundaries. As the panel is mounted rotated, the packing runs
+    /// along the display's rows rather than its columns: `x` and `w` are used
+    /// exactly, while `y` and `h` grow to whole 4-row groups counted from the
+    /// bottom edge. For example `y = 5, h = 3` sends rows 2..10. The window is
+    /// clipped to the screen first.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_partial_xywh<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        x: u16,
+        y: u16,
+        w: u16,
+        h: u16,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let area = Rectangle::new(
+            Point::new(x.into(), y.into()),
+            Size::new(w.into(), h.into()),
+        );
+        self.update_partial(spi, display, area, delay)
+    }
+
+    /// Updates and refreshes just `rect` of the panel in one call; the usual
+    /// choice for dashboard updates.
+    ///
+    /// This is [`update_partial`](Self::update_partial): `rect` is clipped to
+    /// the screen and rounded outward to the 4-pixel RAM alignment, only that
+    /// window is written (the controller has a single RAM, so there is no
+    /// separate base image to send) and only that window is refreshed.
+    ///
+    /// Partial refreshes skip the full waveform, so each one leaves a little
+    /// ghosting behind. To clear it, every [`Builder::full_refresh_every`]
+    /// calls (10 by default) this does a full refresh of the whole `display`
+    /// instead; keep that enabled unless you schedule full refreshes yourself.
+    pub fn refresh_region<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        rect: Rectangle,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.update_partial(spi, display, rect, delay)
+    }
+
+    /// Sets the VCOM and data interval (the low nibble of the CDI register) and
+    /// writes it to the panel right away. Only the low 4 bits of `interval` are
+    /// used; the default is 0x7.
+    ///
+    /// The CDI register (0x50) is laid out as:
+    ///
+    /// | bits | field                                             | default |
+    /// |------|---------------------------------------------------|---------|
+    /// | 7..4 | border output and data polarity                   | 0x3     |
+    /// | 3..0 | VCOM and data interval, 0x0 = 17 .. 0xF = 2 lines | 0x7     |
+    ///
+    /// A shorter interval (higher value) makes the black/white flashing during a
+    /// refresh less pronounced, at the cost of some ghosting. The border bits are
+    /// left untouched, and the value survives [`soft_reset`](Self::soft_reset).
+    pub fn set_data_interval<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        interval: u8,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.cdi = (self.cdi & 0xF0) | (interval & 0x0F);
+        self.command(spi, 0x50, &[self.cdi])
+    }
+
+    /// Roughly how long a refresh in `mode` will block, in milliseconds, e.g.
+    /// to show "updating..." or keep a watchdog fed.
+    ///
+    /// Returns the [`last_refresh_ms`](Self::last_refresh_ms) for that mode once
+    /// one has been timed, and a conservative default before that.
+    pub fn frame_time_estimate(&self, mode: RefreshMode) -> u32 {
+        self.last_refresh_ms(mode).unwrap_or(match mode {
+            RefreshMode::Full => FULL_REFRESH_ESTIMATE_MS,
+            RefreshMode::Partial => PARTIAL_REFRESH_ESTIMATE_MS,
+        })
+    }
+
+    /// Roughly how long sending `bytes` takes at the
+    /// [`Builder::spi_frequency_hint`], in milliseconds (rounded up), or `None`
+    /// without a hint. A full frame ([`RAM_BYTES`]) takes about 16 ms at 4 MHz.
+    ///
+    /// This counts only the clocked bits; per-transfer overhead from the HAL
+    /// comes on top.
+    pub fn transfer_time_estimate(&self, bytes: usize) -> Option<u32> {
+        let hz = self.config.spi_hz.filter(|&hz| hz != 0)? as u64;
+        Some((bytes as u64 * 8 * 1_000).div_ceil(hz) as u32)
+    }
+
+    /// How long the last refresh in `mode` kept the panel busy, in
+    /// milliseconds, or `None` if there hasn't been one yet.
+    pub fn last_refresh_ms(&self, mode: RefreshMode) -> Option<u32> {
+        match mode {
+            RefreshMode::Full => self.last_full_ms,
+            RefreshMode::Partial => self.last_partial_ms,
+        }
+    }
+
+    /// Shows `next` on a panel currently showing `prev`, refreshing only the
+    /// area where they differ.
+    ///
+    /// The changed area from [`DisplayBuffer::diff_bounds`] is sent through
+    /// [`update_partial`](Self::update_partial); the controller has a single
+    /// 2-bit RAM, so there is only one window to write. Partial refreshes skip
+    /// the full waveform and ghost a little each time, so when the change covers
+    /// more than [`Builder::diff_full_fraction`] of the screen this does a full
+    /// [`update_frames`](Self::update_frames) + [`display_frame`](Self::display_frame)
+    /// instead. Identical buffers send nothing.
+    pub fn update_diff<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        prev: &DisplayBuffer,
+        next: &DisplayBuffer,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let Some(area) = prev.diff_bounds(next) else {
+            return Ok(());
+        };
+        let fraction = area.size.width as f32 * area.size.height as f32 / (WIDTH * HEIGHT) as f32;
+        if fraction > self.config.diff_full_fraction {
+            self.update_frames(spi, next)?;
+            return self.display_frame(spi, delay);
+        }
+        self.update_partial(spi, next, area, delay)
+    }
+
+    /// Number of partial refreshes since the last full refresh.
+    pub fn partials_since_full(&self) -> u32 {
+        self.partials_since_full
+    }
+
+    fn refresh_window<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        window: &RamWindow,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.power != PowerState::On {
+            return Err(Jd79661Error::NotPowered);
+        }
+
+        let every = self.config.full_refresh_every;
+        if every != 0 && self.partials_since_full >= every {
+            self.update_frames(spi, display)?;
+            return self.display_frame(spi, delay);
+        }
+
+        self.write_window(spi, display, window)?;
+        self.refresh_partial(spi, delay)?;
+        self.partials_since_full += 1;
+        Ok(())
+    }
+
+    fn write_window<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        display: &DisplayBuffer,
+        window: &RamWindow,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.frame_crc = None;
+        self.command(spi, 0x91, &[])?; // Partial In
+        self.command(spi, 0x90, &window.to_bytes())?; // Partial Window
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+
+        let mut row = [0u8; RAM_ROW_BYTES];
+        let mut result = Ok(());
+        for gate in window.gate_start..window.gate_end {
+            let mut len = 0;
+            for src in (window.src_start..window.src_end).step_by(4) {
+                row[len] = pack_ram_byte(display, gate, src);
+                len += 1;
+            }
+            result = self.write_spi(spi, &row[..len]);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.end_transfer(result)
+    }
+
+    fn refresh_partial<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let result = self
+            .command(spi, 0x12, &[]) // Display Refresh
+            .and_then(|()| self.wait_busy(delay, PARTIAL_REFRESH_ESTIMATE_MS, &mut || {}));
+        // Leave partial mode even if the refresh failed or timed out, or the
+        // next full refresh would only cover the stale window.
+        let partial_out = self.command_unchecked(spi, 0x92, &[]); // Partial Out
+        self.last_partial_ms = Some(result?);
+        partial_out
+    }
+
+    /// Writes an all-white frame to RAM and does a full refresh, without
+    /// needing a [`DisplayBuffer`].
+    pub fn clear_display<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.clear_ram(spi, QuadColor::White)?;
+        self.display_frame(spi, delay)
+    }
+
+    /// Clears the panel to white as quickly as possible, e.g. at boot right
+    /// before drawing the real content with a full refresh.
+    ///
+    /// The controller has no separate fast waveform, so this runs the shorter
+    /// partial-refresh waveform over the whole RAM instead of the full
+    /// anti-ghosting one, saving several seconds. It may leave faint ghosts of
+    /// the previous image, which the next [`display_frame`](Self::display_frame)
+    /// clears. It counts towards [`Builder::full_refresh_every`] like any
+    /// partial refresh.
+    pub fn blank_white_fast<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.power != PowerState::On {
+            return Err(Jd79661Error::NotPowered);
+        }
+        let window = RamWindow {
+            gate_start: 0,
+            gate_end: RAM_GATES,
+            src_start: 0,
+            src_end: RAM_SOURCES,
+        };
+        self.frame_crc = None;
+        self.command(spi, 0x91, &[])?; // Partial In
+        self.command(spi, 0x90, &window.to_bytes())?; // Partial Window
+        self.command(spi, 0x10, &[])?;
+
+        self.begin_data()?;
+        let row = [QuadColor::White.ram_code() * 0b0101_0101; RAM_ROW_BYTES];
+        let mut result = Ok(());
+        for _ in 0..RAM_GATES {
+            result = self.write_spi(spi, &row);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.end_transfer(result)?;
+
+        self.refresh_partial(spi, delay)?;
+        self.partials_since_full += 1;
+        Ok(())
+    }
+
+    /// Fills the panel RAM with `color` without refreshing, e.g. to wipe it
+    /// right before a full redraw. Nothing changes on screen until the next
+    /// [`display_frame`](Self::display_frame).
+    pub fn clear_ram<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+        color: QuadColor,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let byte = color.ram_code() * 0b0101_0101; // The code in all four pixels
+        self.update_from_reader(spi, |_| byte)
+    }
+
+    pub fn display_frame<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.display_frame_with(spi, delay, || {})
+    }
+
+    /// Starts a full refresh and returns right away instead of waiting for it,
+    /// so the caller can poll [`refresh_done`](Self::refresh_done) or cancel
+    /// with [`abort_refresh`](Self::abort_refresh).
+    ///
+    /// Don't send anything else to the panel until the refresh is done. The
+    /// refresh isn't timed for [`last_refresh_ms`](Self::last_refresh_ms).
+    /// Without a BUSY line completion can't be polled; wait out the full
+    /// refresh time (about 20 s) instead.
+    pub fn start_refresh<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.power != PowerState::On {
+            return Err(Jd79661Error::NotPowered);
+        }
+        self.command(spi, 0x12, &[])?; // Display Refresh
+        self.partials_since_full = 0;
+        Ok(())
+    }
+
+    /// Whether a refresh begun with [`start_refresh`](Self::start_refresh)
+    /// has finished, i.e. BUSY has released. Fails with
+    /// [`Jd79661Error::NoBusyLine`] on panels set up with
+    /// [`Jd79661::new_no_busy`], where the end of a refresh can't be
+    /// observed.
+    ///
+    /// BUSY can take a moment to assert after the refresh command, so leave a
+    /// few milliseconds before the first poll.
+    pub fn refresh_done<E>(&mut self) -> Result<bool, Jd79661Error<E>> {
+        if !self.busy_wired {
+            return Err(Jd79661Error::NoBusyLine);
+        }
+        Ok(!self.is_busy()?)
+    }
+
+    /// Like [`display_frame`](Self::display_frame), but calls `yield_now`
+    /// every millisecond while the refresh is running.
+    ///
+    /// A refresh keeps BUSY asserted for many seconds; in a cooperative
+    /// scheduler `yield_now` can hand control back so other tasks keep running
+    /// while the blocking driver waits.
+    pub fn display_frame_with<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+        mut yield_now: impl FnMut(),
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        if self.power != PowerState::On {
+            return Err(Jd79661Error::NotPowered);
+        }
+        self.command(spi, 0x12, &[])?; // Display Refresh
+        self.last_full_ms =
+            Some(self.wait_busy(delay, FULL_REFRESH_ESTIMATE_MS, &mut yield_now)?);
+        self.partials_since_full = 0;
+        Ok(())
+    }
+}
+
+/// Source outputs (RAM columns) of the controller; display y runs along these.
+const RAM_SOURCES: usize = 128;
+/// Gate lines (RAM rows) of the controller; display x runs along these.
+pub const RAM_GATES: usize = 250;
+
+/// Bytes per gate line in the 2-bit-per-pixel RAM.
+pub const RAM_ROW_BYTES: usize = RAM_SOURCES / 4;
+
+/// Size of a full packed frame in the controller RAM (128 x 250 pixels at
+/// 2 bits each), as taken by [`Jd79661::write_raw_frame`].
+pub const RAM_BYTES: usize = RAM_ROW_BYTES * RAM_GATES;
+
+/// Fails the build unless a frame asset is exactly [`RAM_BYTES`] long, so a
+/// wrong-sized file can't reach [`Jd79661::write_raw_frame`] at runtime.
+///
+/// The argument must be usable in a constant, e.g. `include_bytes!` or a
+/// `const` item:
+///
+/// ```ignore
+/// const SPLASH: &[u8] = include_bytes!("splash.bin");
+/// assert_frame_size!(SPLASH);
+/// ```
+#[macro_export]
+macro_rules! assert_frame_size {
+    ($frame:expr) => {
+        const _: () = assert!(
+            $frame.len() == $crate::RAM_BYTES,
+            "frame asset is not RAM_BYTES long"
+        );
+    };
+}
+
+/// Estimates used by [`Jd79661::frame_time_estimate`] until a refresh has
+/// been timed, and as the fixed refresh waits without a BUSY line.
+const FULL_REFRESH_ESTIMATE_MS: u32 = 20_000;
+const PARTIAL_REFRESH_ESTIMATE_MS: u32 = 5_000;
+
+/// Fixed wait for reset and power switching when BUSY isn't wired.
+const FIXED_INIT_WAIT_MS: u32 = 1_000;
+
+/// CDI register (0x50) value from Adafruit's init sequence.
+const CDI_DEFAULT: u8 = 0x37;
+
+/// Packs gate lines `gates` of `display` into `out` exactly as
+/// [`Jd79661::update_frames`] sends them, [`RAM_ROW_BYTES`] bytes per line,
+/// and returns the number of bytes written.
+///
+/// Concatenating the chunks for `0..RAM_GATES` gives the whole frame, so
+/// packing can be pipelined with the transfer (e.g. packing the next block on
+/// the second core while the previous one goes out through
+/// [`Jd79661::update_from_iter`]). Lines beyond [`RAM_GATES`] or that don't
+/// fit in `out` are skipped.
+pub fn pack_frame_chunk(display: &DisplayBuffer, gates: Range<usize>, out: &mut [u8]) -> usize {
+    let gates = gates.start.min(RAM_GATES)..gates.end.min(RAM_GATES);
+    let mut written = 0;
+    for (gate, line) in gates.zip(out.chunks_exact_mut(RAM_ROW_BYTES)) {
+        for (byte, src) in line.iter_mut().zip((0..RAM_SOURCES).step_by(4)) {
+            *byte = pack_ram_byte(display, gate, src);
+        }
+        written += RAM_ROW_BYTES;
+    }
+    written
+}
+
+/// Packs the four pixels at RAM sources `src..src + 4` of gate line `gate`.
+///
+/// The panel is mounted rotated, so gate lines map to display x and sources
+/// map to display y counted from the bottom edge.
+fn pack_ram_byte(display: &DisplayBuffer, gate: usize, src: usize) -> u8 {
+    let mut byte = 0u8;
+    for rx in src..src + 4 {
+        let color_bits = if gate < WIDTH && rx < HEIGHT {
+            display.color_at(gate, HEIGHT - 1 - rx).ram_code()
+        } else {
+            1 // Padding (White)
+        };
+        byte = (byte << 2) | color_bits;
+    }
+    byte
+}
+
+/// CRC-32 of an already packed frame, matching [`frame_crc`].
+fn packed_crc(frame: &[u8]) -> u32 {
+    !frame.iter().fold(!0, |crc, &byte| crc32_update(crc, byte))
+}
+
+/// CRC-32 of the frame [`Jd79661::update_frames`] would send for `display`.
+fn frame_crc(display: &DisplayBuffer) -> u32 {
+    let mut crc = !0;
+    for gate in 0..RAM_GATES {
+        for src in (0..RAM_SOURCES).step_by(4) {
+            crc = crc32_update(crc, pack_ram_byte(display, gate, src));
+        }
+    }
+    !crc
+}
+
+/// A rectangle of controller RAM, in gate lines and (4-pixel aligned) sources.
+struct RamWindow {
+    gate_start: usize,
+    gate_end: usize,
+    src_start: usize,
+    src_end: usize,
+}
+
+impl RamWindow {
+    /// Covers the display pixels `x0..x1`, `y0..y1`, rounded outward to the
+    /// 4-pixel byte boundaries of the RAM packing.
+    fn covering(x0: usize, y0: usize, x1: usize, y1: usize) -> Self {
+        Self {
+            gate_start: x0,
+            gate_end: x1,
+            src_start: ((HEIGHT - y1) / 4) * 4,
+            src_end: (HEIGHT - y0).div_ceil(4) * 4,
+        }
+    }
+
+    /// Bounding window of the pixels that differ between two copies of a plane.
+    fn changed(old: &[u8; BUF_SIZE], new: &[u8; BUF_SIZE]) -> Option<Self> {
+        changed_bounds(old, new).map(|(x0, y0, x1, y1)| Self::covering(x0, y0, x1, y1))
+    }
+
+    /// Partial Window (0x90) payload: inclusive start/end of sources, then gates.
+    fn to_bytes(&self) -> [u8; 9] {
+        let (hs, he) = (self.src_start as u16, self.src_end as u16 - 1);
+        let (vs, ve) = (self.gate_start as u16, self.gate_end as u16 - 1);
+        [
+            (hs >> 8) as u8,
+            hs as u8,
+            (he >> 8) as u8,
+            he as u8,
+            (vs >> 8) as u8,
+            vs as u8,
+            (ve >> 8) as u8,
+            ve as u8,
+            0x01, // Scan inside the window only
+        ]
+    }
+}
+
+/// Bounds `(x0, y0, x1, y1)` (exclusive ends) of the pixels whose bits differ
+/// between two copies of a plane, or `None` if they are identical.
+fn changed_bounds(
+    old: &[u8; BUF_SIZE],
+    new: &[u8; BUF_SIZE],
+) -> Option<(usize, usize, usize, usize)> {
+    let (mut x0, mut y0, mut x1, mut y1) = (WIDTH, HEIGHT, 0, 0);
+    for (i, (a, b)) in old.iter().zip(new.iter()).enumerate() {
+        let diff = a ^ b;
+        if diff == 0 {
+            continue;
+        }
+        for bit in 0..8 {
+            if diff & (0x80 >> bit) == 0 {
+                continue;
+            }
+            let pixel = i * 8 + bit;
+            if pixel >= WIDTH * HEIGHT {
+                break;
+            }
+            let (x, y) = (pixel % WIDTH, pixel / WIDTH);
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x + 1);
+            y1 = y1.max(y + 1);
+        }
+    }
+    (x1 > x0).then_some((x0, y0, x1, y1))
+}
+
+pub const WIDTH: usize = 250;
+pub const HEIGHT: usize = 122;
+pub const BUF_SIZE: usize = (WIDTH * HEIGHT).div_ceil(8);
+
+/// Errors returned by [`DisplayBuffer`] methods taking raw pixel data.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BufferError {
+    /// The data wasn't exactly one plane ([`BUF_SIZE`] bytes) long, or for
+    /// [`DisplayBuffer::draw_bitmap_packed`] and
+    /// [`DisplayBuffer::draw_indexed`] not a whole number of rows.
+    Length { expected: usize, actual: usize },
+}
+
+#[derive(Clone, Debug)]
+pub struct DisplayBuffer {
+    pub bw: [u8; BUF_SIZE],
+    pub red: [u8; BUF_SIZE],
+    pub yellow: [u8; BUF_SIZE],
+    tri_color: bool,
+    background: QuadColor,
 }
 
-impl<CS, BUSY, DC, RST> Jd79661<CS, BUSY, DC, RST>
-where
-    CS: OutputPin,
-    BUSY: InputPin,
-    DC: OutputPin,
-    RST: OutputPin + StatefulOutputPin,
-{
-    pub fn new<SPI, DELAY>(
-        spi: &mut SPI,
-        cs: CS,
-        busy: BUSY,
-        dc: DC,
-        mut rst: RST,
-        delay: &mut DELAY,
-    ) -> Result<Self, SPI::Error>
-    where
-        SPI: SpiDevice,
-        DELAY: DelayNs,
-    {
-        // Hardware reset
-        let _ = rst.set_low();
-        delay.delay_ms(10);
-        let _ = rst.set_high();
-        delay.delay_ms(10);
+impl DisplayBuffer {
+    pub fn new() -> Self {
+        Self {
+            bw: [0xFF; BUF_SIZE],     // All white (inverted logic: 1=White, 0=Black)
+            red: [0xFF; BUF_SIZE],    // All clear (1=Clear, 0=Red)
+            yellow: [0xFF; BUF_SIZE], // All clear (1=Clear, 0=Yellow)
+            tri_color: false,
+            background: QuadColor::White,
+        }
+    }
 
-        let mut driver = Self {
-            cs,
-            busy,
-            dc,
-            _rst: rst,
-        };
+    /// Creates a buffer filled with `background` that [`clear`](Self::clear)
+    /// also resets to, e.g. for a dark theme.
+    pub fn with_background(background: QuadColor) -> Self {
+        let mut buffer = Self::new();
+        buffer.set_background(background);
+        buffer.clear();
+        buffer
+    }
 
-        driver.wait_busy(delay);
-        driver.command(spi, 0x01, &[])?; // SWRESET
-        driver.wait_busy(delay);
+    /// Sets the color [`clear`](Self::clear) resets to; white by default, the
+    /// panel's cleared state. The pixels already drawn are left alone.
+    pub fn set_background(&mut self, background: QuadColor) {
+        self.background = background;
+    }
 
-        // Magic key from Adafruit driver
-        driver.command(spi, 0x4D, &[0x78])?;
+    pub fn background(&self) -> QuadColor {
+        self.background
+    }
 
-        // Panel Setting (128x250 resolution)
-        driver.command(spi, 0x00, &[0x8F, 0x29])?;
+    /// Creates a white buffer in tri-color mode, see
+    /// [`set_tri_color`](Self::set_tri_color).
+    pub fn new_tri_color() -> Self {
+        let mut buffer = Self::new();
+        buffer.tri_color = true;
+        buffer
+    }
 
-        // Power setting
-        driver.command(spi, 0x01, &[0x07, 0x00])?;
+    /// Turns tri-color (black/white/red) mode on or off, for reusing drawing
+    /// code written for tri-color panels such as the SSD1681.
+    ///
+    /// While on, anything drawn as `QuadColor::Yellow` (pixels, fills, text,
+    /// images) is drawn white instead: yellow is the lightest ink, so a
+    /// tri-color design that never expected it keeps its contrast. Pixels
+    /// already in the buffer, or written to the planes directly, are left
+    /// alone.
+    pub fn set_tri_color(&mut self, enabled: bool) {
+        self.tri_color = enabled;
+    }
 
-        // Power offset
-        driver.command(spi, 0x03, &[0x10, 0x54, 0x44])?;
+    pub fn is_tri_color(&self) -> bool {
+        self.tri_color
+    }
 
-        // Booster Soft Start
-        driver.command(spi, 0x06, &[0x05, 0x00, 0x3F, 0x0A, 0x25, 0x12, 0x1A])?;
+    /// The color actually drawn for `color` in the current mode.
+    fn resolve(&self, color: QuadColor) -> QuadColor {
+        match color {
+            QuadColor::Yellow if self.tri_color => QuadColor::White,
+            color => color,
+        }
+    }
 
-        // CDI
-        driver.command(spi, 0x50, &[0x37])?;
+    /// Creates a buffer with every pixel set to `color`.
+    pub fn new_filled(color: QuadColor) -> Self {
+        let mut buffer = Self::new();
+        buffer.fill(color);
+        buffer
+    }
 
-        // TCON
-        driver.command(spi, 0x60, &[0x02, 0x02, 0x02])?;
+    /// A white buffer with `pixels` drawn into it, e.g. from a plot
+    /// computation. Pixels outside the panel are skipped.
+    pub fn from_pixels(pixels: impl IntoIterator<Item = Pixel<QuadColor>>) -> Self {
+        let mut buffer = Self::new();
+        let _ = buffer.draw_iter(pixels);
+        buffer
+    }
 
-        // Resolution (128 x 250)
-        driver.command(spi, 0x61, &[0x00, 0x80, 0x00, 0xFA])?;
+    /// Resets every pixel to the [`background`](Self::background), white
+    /// unless changed.
+    pub fn clear(&mut self) {
+        self.fill(self.background);
+    }
 
-        // Additional config registers from Adafruit
-        driver.command(spi, 0xE7, &[0x1C])?;
-        driver.command(spi, 0xE3, &[0x22])?;
-        driver.command(spi, 0xB4, &[0xD0])?;
-        driver.command(spi, 0xB5, &[0x03])?;
-        driver.command(spi, 0xE9, &[0x01])?;
-        driver.command(spi, 0x30, &[0x08])?;
+    /// A copy of the buffer to roll back to with [`restore`](Self::restore),
+    /// e.g. after drawing something that then fails validation.
+    ///
+    /// This copies all three planes (about 11 KiB), so keep the snapshot on
+    /// the stack only where there is room for a second buffer.
+    pub fn snapshot(&self) -> DisplayBuffer {
+        self.clone()
+    }
 
-        // Power ON
-        driver.command(spi, 0x04, &[])?;
-        driver.wait_busy(delay);
+    /// Puts back the contents (and tri-color mode) of an earlier
+    /// [`snapshot`](Self::snapshot).
+    pub fn restore(&mut self, snapshot: &DisplayBuffer) {
+        self.clone_from(snapshot);
+    }
 
-        Ok(driver)
+    /// The black/white, red and yellow planes, in that order.
+    pub fn as_planes(&self) -> (&[u8; BUF_SIZE], &[u8; BUF_SIZE], &[u8; BUF_SIZE]) {
+        (&self.bw, &self.red, &self.yellow)
     }
 
-    fn command<SPI: SpiDevice>(
+    /// Consumes the buffer and returns its black/white, red and yellow planes
+    /// by value, e.g. to hand a rendered frame to another core or a DMA
+    /// transfer without keeping the buffer borrowed.
+    pub fn into_planes(self) -> ([u8; BUF_SIZE], [u8; BUF_SIZE], [u8; BUF_SIZE]) {
+        (self.bw, self.red, self.yellow)
+    }
+
+    /// Replaces the black/white plane with `data`, which must be exactly
+    /// [`BUF_SIZE`] bytes in the same layout (a 0 bit is black).
+    pub fn load_bw(&mut self, data: &[u8]) -> Result<(), BufferError> {
+        load_plane(&mut self.bw, data)
+    }
+
+    /// Replaces the red plane with `data` (a 0 bit is red), like
+    /// [`load_bw`](Self::load_bw).
+    pub fn load_red(&mut self, data: &[u8]) -> Result<(), BufferError> {
+        load_plane(&mut self.red, data)
+    }
+
+    /// Replaces the yellow plane with `data` (a 0 bit is yellow), like
+    /// [`load_bw`](Self::load_bw).
+    pub fn load_yellow(&mut self, data: &[u8]) -> Result<(), BufferError> {
+        load_plane(&mut self.yellow, data)
+    }
+
+    /// Sets every pixel to `color`, writing whole bytes of each plane.
+    pub fn fill(&mut self, color: QuadColor) {
+        let color = self.resolve(color);
+        self.bw.fill(if color == QuadColor::Black {
+            0x00
+        } else {
+            0xFF
+        });
+        self.red
+            .fill(if color == QuadColor::Red { 0x00 } else { 0xFF });
+        self.yellow.fill(if color == QuadColor::Yellow {
+            0x00
+        } else {
+            0xFF
+        });
+    }
+
+    /// Fills `area` (clipped to the buffer) with `color`, writing whole bytes
+    /// of each plane where the row span allows.
+    pub fn fill_rect(&mut self, area: Rectangle, color: QuadColor) {
+        let color = self.resolve(color);
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+        let (x, width) = (area.top_left.x as usize, area.size.width as usize);
+        for y in area.top_left.y as usize..=bottom_right.y as usize {
+            let start = y * WIDTH + x;
+            set_span(&mut self.bw, start, width, color == QuadColor::Black);
+            set_span(&mut self.red, start, width, color == QuadColor::Red);
+            set_span(&mut self.yellow, start, width, color == QuadColor::Yellow);
+        }
+    }
+
+    /// Draws an image of pre-packed 2-bit color codes with its top-left corner
+    /// at `origin`, clipping whatever falls outside the buffer.
+    ///
+    /// `data` uses the [`region_hash`](Self::region_hash) packing: the RAM
+    /// codes of [`QuadColor::to_u8`] (as sent by
+    /// [`Jd79661::update_frames`]), four pixels per byte with the leftmost in
+    /// the top bits, row by row from the top, each row padded to a whole byte.
+    /// The height follows from the length, which must be a whole number of
+    /// `src_width.div_ceil(4)`-byte rows.
+    pub fn draw_bitmap_packed(
         &mut self,
-        spi: &mut SPI,
-        cmd: u8,
         data: &[u8],
-    ) -> Result<(), SPI::Error> {
-        let _ = self.dc.set_low();
-        let _ = self.cs.set_low();
-        spi.write(&[cmd])?;
-        let _ = self.cs.set_high();
-
-        if !data.is_empty() {
-            let _ = self.dc.set_high();
-            let _ = self.cs.set_low();
-            spi.write(data)?;
-            let _ = self.cs.set_high();
+        src_width: usize,
+        origin: Point,
+    ) -> Result<(), BufferError> {
+        let row_bytes = src_width.div_ceil(4);
+        let rows = data.len().checked_div(row_bytes).unwrap_or(0);
+        if rows * row_bytes != data.len() {
+            return Err(BufferError::Length {
+                expected: (rows + 1) * row_bytes,
+                actual: data.len(),
+            });
         }
+
+        let pixels = data
+            .chunks_exact(row_bytes.max(1))
+            .enumerate()
+            .flat_map(|(y, row)| {
+                (0..src_width).map(move |x| {
+                    let code = (row[x / 4] >> (6 - 2 * (x % 4))) & 0b11;
+                    let color = QuadColor::from_u8(code).unwrap_or_default();
+                    Pixel(origin + Point::new(x as i32, y as i32), color)
+                })
+            });
+        let _ = self.draw_iter(pixels);
         Ok(())
     }
 
-    fn wait_busy<DELAY: DelayNs>(&mut self, delay: &mut DELAY) {
-        // Based on adafruit_jd79661.py, busy_state=False
-        // This means it is BUSY when LOW.
-        while self.busy.is_low().unwrap_or(false) {
-            delay.delay_ms(1);
+    /// Sets every pixel whose bit is 1 in `mask` to `color` and leaves the rest
+    /// untouched, e.g. to stamp an icon or apply a stencil.
+    ///
+    /// `mask` uses the plane layout (row-major, MSB first, one bit per pixel),
+    /// so its length is checked by its type and the work is done a byte at a
+    /// time. Padding bits are ignored.
+    pub fn apply_mask(&mut self, mask: &[u8; BUF_SIZE], color: QuadColor) {
+        let color = self.resolve(color);
+        let planes = [
+            (&mut self.bw, QuadColor::Black),
+            (&mut self.red, QuadColor::Red),
+            (&mut self.yellow, QuadColor::Yellow),
+        ];
+        for (plane, ink) in planes {
+            for (i, (byte, &bits)) in plane.iter_mut().zip(mask.iter()).enumerate() {
+                let bits = if i == BUF_SIZE - 1 {
+                    bits & PAD_MASK
+                } else {
+                    bits
+                };
+                if color == ink {
+                    *byte &= !bits;
+                } else {
+                    *byte |= bits;
+                }
+            }
         }
     }
 
-    pub fn update_frames<SPI: SpiDevice>(
-        &mut self,
-        spi: &mut SPI,
-        display: &DisplayBuffer,
-    ) -> Result<(), SPI::Error> {
-        // Send command to start transmission
-        self.command(spi, 0x10, &[])?;
+    /// Rotates the image by 180° in place, for panels mounted upside down.
+    ///
+    /// Each plane's pixel order is reversed. Rows aren't byte aligned
+    /// (250 px) and the last byte carries padding, so this swaps pixels rather
+    /// than reversing bytes. Applying it twice restores the original buffer.
+    pub fn rotate_180(&mut self) {
+        for plane in [&mut self.bw, &mut self.red, &mut self.yellow] {
+            let (mut a, mut b) = (0, WIDTH * HEIGHT - 1);
+            while a < b {
+                swap_bits(plane, a, b);
+                a += 1;
+                b -= 1;
+            }
+        }
+    }
 
-        let _ = self.dc.set_high();
-        let _ = self.cs.set_low();
-
-        // 128x250 RAM.
-        for ly_as_rx in 0..250 {
-            for lx_as_ry_block in (0..128).step_by(4) {
-                let mut byte = 0u8;
-                for i in 0..4 {
-                    let rx = lx_as_ry_block + i;
-                    let ry = ly_as_rx;
-
-                    let color_bits = if ry < 250 && rx < 122 {
-                        let x = ry;
-                        let y = 121 - rx;
-
-                        let idx = (y * WIDTH + x) / 8;
-                        let bit = 7 - (x % 8);
-
-                        let bw = (display.bw[idx] >> bit) & 1;
-                        let red = (display.red[idx] >> bit) & 1;
-                        let yellow = (display.yellow[idx] >> bit) & 1;
-
-                        // Mapping corrected based on hardware observation:
-                        // 00 -> Black
-                        // 01 -> White
-                        // 10 -> Yellow
-                        // 11 -> Red
-                        if red == 0 {
-                            3 // Red (11)
-                        } else if yellow == 0 {
-                            2 // Yellow (10)
-                        } else if bw == 0 {
-                            0 // Black (00)
-                        } else {
-                            1 // White (01)
-                        }
-                    } else {
-                        1 // Padding (Yellow?)
-                    };
-                    byte = (byte << 2) | color_bits;
+    /// Mirrors the image left to right in place, e.g. for viewing through a
+    /// mirror or rear projection. Applying it twice restores the original.
+    pub fn mirror_x(&mut self) {
+        for plane in [&mut self.bw, &mut self.red, &mut self.yellow] {
+            for row in (0..HEIGHT).map(|y| y * WIDTH) {
+                for x in 0..WIDTH / 2 {
+                    swap_bits(plane, row + x, row + WIDTH - 1 - x);
                 }
-                spi.write(&[byte])?;
             }
         }
-        let _ = self.cs.set_high();
+    }
+
+    /// Mirrors the image top to bottom in place. Applying it twice restores
+    /// the original.
+    ///
+    /// Rows aren't byte aligned, so whole rows are swapped pixel by pixel.
+    pub fn mirror_y(&mut self) {
+        for plane in [&mut self.bw, &mut self.red, &mut self.yellow] {
+            for y in 0..HEIGHT / 2 {
+                let (top, bottom) = (y * WIDTH, (HEIGHT - 1 - y) * WIDTH);
+                for x in 0..WIDTH {
+                    swap_bits(plane, top + x, bottom + x);
+                }
+            }
+        }
+    }
+
+    /// Writes a downscaled ASCII preview, one character per `scale` x `scale`
+    /// cell sampled at its top-left pixel: `#` black, ` ` white, `R` red and
+    /// `Y` yellow.
+    ///
+    /// Lines end in `\r\n` for serial terminals. A `scale` of 4 fits the
+    /// 250 px width into 63 columns.
+    pub fn dump_ascii(&self, out: &mut impl core::fmt::Write, scale: usize) -> core::fmt::Result {
+        let scale = scale.max(1);
+        for y in (0..HEIGHT).step_by(scale) {
+            for x in (0..WIDTH).step_by(scale) {
+                out.write_char(self.color_at(x, y).into())?;
+            }
+            out.write_str("\r\n")?;
+        }
         Ok(())
     }
 
-    pub fn display_frame<SPI: SpiDevice, DELAY: DelayNs>(
+    /// Reports how much of the screen each dark ink covers, flagging frames where
+    /// one exceeds [`SAFE_COVERAGE`].
+    pub fn coverage(&self) -> CoverageReport {
+        self.coverage_with_limit(SAFE_COVERAGE)
+    }
+
+    /// Like [`coverage`](Self::coverage), with a custom fraction (`0.0..=1.0`)
+    /// above which a single ink counts as unsafe.
+    pub fn coverage_with_limit(&self, limit: f32) -> CoverageReport {
+        let (mut black, mut red, mut yellow) = (0u32, 0u32, 0u32);
+        for i in 0..BUF_SIZE {
+            let valid = if i == BUF_SIZE - 1 { PAD_MASK } else { 0xFF };
+            let (bw, r, y) = (self.bw[i], self.red[i], self.yellow[i]);
+            red += (!r & valid).count_ones();
+            yellow += (r & !y & valid).count_ones();
+            black += (r & y & !bw & valid).count_ones();
+        }
+
+        let total = (WIDTH * HEIGHT) as f32;
+        let (black, red, yellow) = (
+            black as f32 / total,
+            red as f32 / total,
+            yellow as f32 / total,
+        );
+        CoverageReport {
+            black,
+            red,
+            yellow,
+            exceeds_safe_coverage: black > limit || red > limit || yellow > limit,
+        }
+    }
+
+    /// Checksums the pixels inside `rect`, for cheaply telling whether a UI
+    /// region changed between frames.
+    ///
+    /// The hash is the CRC-32 (IEEE, as used by zlib) of the region's colors
+    /// packed like the panel RAM: 2-bit codes, four pixels per byte, row by row
+    /// from the top, with each row padded to a whole byte. It only depends on
+    /// the colors shown, so it is stable across crate versions and can be
+    /// computed off-device. `rect` is clipped to the buffer; an empty region
+    /// hashes to 0.
+    pub fn region_hash(&self, rect: Rectangle) -> u32 {
+        let area = rect.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return 0;
+        };
+
+        let mut crc = !0;
+        for y in area.top_left.y as usize..=bottom_right.y as usize {
+            let (mut byte, mut pixels) = (0u8, 0);
+            for x in area.top_left.x as usize..=bottom_right.x as usize {
+                byte = (byte << 2) | self.color_at(x, y).ram_code();
+                pixels += 1;
+                if pixels == 4 {
+                    crc = crc32_update(crc, byte);
+                    (byte, pixels) = (0, 0);
+                }
+            }
+            if pixels != 0 {
+                crc = crc32_update(crc, byte << (2 * (4 - pixels)));
+            }
+        }
+        !crc
+    }
+
+    /// CRC-32 of the whole buffer, i.e. [`region_hash`](Self::region_hash)
+    /// over all [`WIDTH`] x [`HEIGHT`] pixels.
+    ///
+    /// The polynomial is the reflected IEEE one (0xEDB88320, init and final
+    /// XOR 0xFFFFFFFF), so a sender can compute it with any zlib-compatible
+    /// CRC-32 over the same 2-bit packing: 63 bytes per row (the last one
+    /// holding two pixels and zero padding), 122 rows from the top.
+    pub fn crc(&self) -> u32 {
+        self.region_hash(self.bounding_box())
+    }
+
+    /// CRC-32 of the packed frame [`Jd79661::update_frames`] sends for this
+    /// buffer, comparable with [`Jd79661::current_frame_crc`]. Unlike
+    /// [`crc`](Self::crc) it covers the panel's RAM layout, padding included.
+    pub fn frame_crc(&self) -> u32 {
+        frame_crc(self)
+    }
+
+    /// Whether the buffer's [`crc`](Self::crc) is `expected`, e.g. to reject a
+    /// frame corrupted in transit before spending a refresh on it.
+    pub fn verify_crc(&self, expected: u32) -> bool {
+        self.crc() == expected
+    }
+
+    /// Flips the pixel at `(x, y)` between colors `a` and `b` and returns its
+    /// new color: `b` if it was `a`, otherwise `a`. Returns `None` if the
+    /// pixel lies outside the buffer.
+    ///
+    /// Pair it with [`Jd79661::update_partial`] on a 1x1 rectangle at the pixel
+    /// to blink an indicator; only the RAM byte holding it (4 pixels) is sent.
+    pub fn toggle_pixel(
         &mut self,
-        spi: &mut SPI,
-        delay: &mut DELAY,
-    ) -> Result<(), SPI::Error> {
-        self.command(spi, 0x12, &[])?; // Display Refresh
-        self.wait_busy(delay);
-        Ok(())
+        x: usize,
+        y: usize,
+        a: QuadColor,
+        b: QuadColor,
+    ) -> Option<QuadColor> {
+        let new = if self.get_pixel(x, y)? == a { b } else { a };
+        let _ = self.draw_iter([Pixel(Point::new(x as i32, y as i32), new)]);
+        Some(new)
+    }
+
+    /// Replaces every pixel inside `rect` with its [`QuadColor::complement`].
+    /// `rect` is clipped to the buffer.
+    pub fn complement_region(&mut self, rect: Rectangle) {
+        let area = rect.intersection(&self.bounding_box());
+        for point in area.points() {
+            let color = self.color_at(point.x as usize, point.y as usize);
+            let _ = self.draw_iter([Pixel(point, color.complement())]);
+        }
+    }
+
+    /// Smallest rectangle containing every pixel whose plane bits differ
+    /// between `self` and `other`, or `None` if the buffers are equal.
+    pub fn diff_bounds(&self, other: &DisplayBuffer) -> Option<Rectangle> {
+        let (mut x0, mut y0, mut x1, mut y1) = (WIDTH, HEIGHT, 0, 0);
+        let planes = [
+            (&self.bw, &other.bw),
+            (&self.red, &other.red),
+            (&self.yellow, &other.yellow),
+        ];
+        for (a, b) in planes {
+            if let Some((px0, py0, px1, py1)) = changed_bounds(a, b) {
+                (x0, y0) = (x0.min(px0), y0.min(py0));
+                (x1, y1) = (x1.max(px1), y1.max(py1));
+            }
+        }
+        (x1 > x0).then(|| {
+            Rectangle::new(
+                Point::new(x0 as i32, y0 as i32),
+                Size::new((x1 - x0) as u32, (y1 - y0) as u32),
+            )
+        })
+    }
+
+    /// Tight bounding rectangle of all pixels showing `color`, or `None` if
+    /// there are none, e.g. to partial-refresh just an alert banner.
+    ///
+    /// Plane bytes are checked eight pixels at a time; only bytes containing
+    /// the color are looked at bit by bit.
+    pub fn bounding_box_of_color(&self, color: QuadColor) -> Option<Rectangle> {
+        let (mut x0, mut y0, mut x1, mut y1) = (WIDTH, HEIGHT, 0, 0);
+        for i in 0..BUF_SIZE {
+            let mut hits = self.color_bits(i, color);
+            while hits != 0 {
+                let pixel = i * 8 + hits.leading_zeros() as usize;
+                hits &= !(0x80 >> hits.leading_zeros());
+                let (px, py) = (pixel % WIDTH, pixel / WIDTH);
+                (x0, y0) = (x0.min(px), y0.min(py));
+                (x1, y1) = (x1.max(px + 1), y1.max(py + 1));
+            }
+        }
+        (x1 > x0).then(|| {
+            Rectangle::new(
+                Point::new(x0 as i32, y0 as i32),
+                Size::new((x1 - x0) as u32, (y1 - y0) as u32),
+            )
+        })
+    }
+
+    /// Rewrites every pixel showing `from` to `to` and leaves all others
+    /// untouched, e.g. to reuse a rendered scene with a different accent.
+    ///
+    /// Works a plane byte (eight pixels) at a time. `to` is resolved like any
+    /// drawn color, so yellow becomes white in tri-color mode.
+    pub fn map_colors(&mut self, from: QuadColor, to: QuadColor) {
+        let to = self.resolve(to);
+        if from == to {
+            return;
+        }
+        for i in 0..BUF_SIZE {
+            let hits = self.color_bits(i, from);
+            if hits == 0 {
+                continue;
+            }
+            for (plane, ink) in [
+                (&mut self.bw, QuadColor::Black),
+                (&mut self.red, QuadColor::Red),
+                (&mut self.yellow, QuadColor::Yellow),
+            ] {
+                if to == ink {
+                    plane[i] &= !hits;
+                } else {
+                    plane[i] |= hits;
+                }
+            }
+        }
+    }
+
+    /// Bits of plane byte `i` whose pixels show `color`, with the padding
+    /// bits masked off.
+    fn color_bits(&self, i: usize, color: QuadColor) -> u8 {
+        let (bw, r, y) = (self.bw[i], self.red[i], self.yellow[i]);
+        let valid = if i == BUF_SIZE - 1 { PAD_MASK } else { 0xFF };
+        valid
+            & match color {
+                QuadColor::Red => !r,
+                QuadColor::Yellow => r & !y,
+                QuadColor::Black => r & y & !bw,
+                QuadColor::White => r & y & bw,
+            }
+    }
+
+    /// Returns the color at `(x, y)`, or `None` if it lies outside the buffer.
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<QuadColor> {
+        (x < WIDTH && y < HEIGHT).then(|| self.color_at(x, y))
+    }
+
+    /// Decodes the planes at an in-bounds pixel. Red wins over yellow, which
+    /// wins over black, matching what the panel shows.
+    fn color_at(&self, x: usize, y: usize) -> QuadColor {
+        let i = y * WIDTH + x;
+        let (idx, bit) = (i / 8, 7 - i % 8);
+
+        if (self.red[idx] >> bit) & 1 == 0 {
+            QuadColor::Red
+        } else if (self.yellow[idx] >> bit) & 1 == 0 {
+            QuadColor::Yellow
+        } else if (self.bw[idx] >> bit) & 1 == 0 {
+            QuadColor::Black
+        } else {
+            QuadColor::White
+        }
     }
 }
 
-pub const WIDTH: usize = 250;
-pub const HEIGHT: usize = 122;
-pub const BUF_SIZE: usize = (WIDTH * HEIGHT).div_ceil(8);
+/// Default limit for [`DisplayBuffer::coverage`]: panel makers warn against
+/// leaving more than this fraction of the screen in one dark ink for long, as
+/// it can burn in.
+pub const SAFE_COVERAGE: f32 = 0.8;
 
-pub struct DisplayBuffer {
-    pub bw: [u8; BUF_SIZE],
-    pub red: [u8; BUF_SIZE],
-    pub yellow: [u8; BUF_SIZE],
+/// Valid pixel bits of the last plane byte; the rest is padding.
+const PAD_MASK: u8 = 0xFF << (BUF_SIZE * 8 - WIDTH * HEIGHT);
+
+/// Fraction of the screen (`0.0..=1.0`) covered by each dark ink.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CoverageReport {
+    pub black: f32,
+    pub red: f32,
+    pub yellow: f32,
+    /// Whether any single ink exceeds the coverage limit.
+    pub exceeds_safe_coverage: bool,
 }
 
-impl DisplayBuffer {
-    pub fn new() -> Self {
-        Self {
-            bw: [0xFF; BUF_SIZE],     // All white (inverted logic: 1=White, 0=Black)
-            red: [0xFF; BUF_SIZE],    // All clear (1=Clear, 0=Red)
-            yellow: [0xFF; BUF_SIZE], // All clear (1=Clear, 0=Yellow)
+/// Clears (`ink`) or sets `len` pixel bits of one plane starting at pixel
+/// index `start`.
+fn set_span(plane: &mut [u8; BUF_SIZE], start: usize, len: usize, ink: bool) {
+    let end = start + len;
+    let mut i = start;
+    while i < end {
+        let (byte, bit) = (i / 8, i % 8);
+        let n = (8 - bit).min(end - i);
+        let mask = (0xFFu8 << (8 - n)) >> bit;
+        if ink {
+            plane[byte] &= !mask;
+        } else {
+            plane[byte] |= mask;
         }
+        i += n;
     }
+}
 
-    pub fn clear(&mut self) {
-        self.bw.fill(0xFF);
-        self.red.fill(0xFF);
-        self.yellow.fill(0xFF);
+/// Swaps the bits of pixel indices `a` and `b` within one plane.
+fn swap_bits(plane: &mut [u8; BUF_SIZE], a: usize, b: usize) {
+    let (ia, ma) = (a / 8, 0x80 >> (a % 8));
+    let (ib, mb) = (b / 8, 0x80 >> (b % 8));
+    let bit_a = plane[ia] & ma != 0;
+    let bit_b = plane[ib] & mb != 0;
+    if bit_a != bit_b {
+        plane[ia] ^= ma;
+        plane[ib] ^= mb;
+    }
+}
+
+fn load_plane(plane: &mut [u8; BUF_SIZE], data: &[u8]) -> Result<(), BufferError> {
+    if data.len() != BUF_SIZE {
+        return Err(BufferError::Length {
+            expected: BUF_SIZE,
+            actual: data.len(),
+        });
+    }
+    plane.copy_from_slice(data);
+    Ok(())
+}
+
+/// Feeds `byte` into a running CRC-32 (IEEE 802.3, reflected polynomial
+/// 0xEDB88320). Start from `!0` and invert the final value.
+const fn crc32_update(mut crc: u32, byte: u8) -> u32 {
+    crc ^= byte as u32;
+    let mut bit = 0;
+    while bit < 8 {
+        crc = if crc & 1 != 0 {
+            (crc >> 1) ^ 0xEDB8_8320
+        } else {
+            crc >> 1
+        };
+        bit += 1;
+    }
+    crc
+}
+
+/// Buffers are equal when their planes hold the same bits; the padding bits
+/// after the last pixel, the tri-color mode and the background are ignored.
+impl PartialEq for DisplayBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        fn plane_eq(a: &[u8; BUF_SIZE], b: &[u8; BUF_SIZE]) -> bool {
+            let last = BUF_SIZE - 1;
+            a[..last] == b[..last] && (a[last] ^ b[last]) & PAD_MASK == 0
+        }
+        plane_eq(&self.bw, &other.bw)
+            && plane_eq(&self.red, &other.red)
+            && plane_eq(&self.yellow, &other.yellow)
     }
 }
 
+impl Eq for DisplayBuffer {}
+
 impl Default for DisplayBuffer {
     fn default() -> Self {
         Self::new()
@@ -228,18 +2548,281 @@ impl PixelColor for QuadColor {
     type Raw = embedded_graphics::pixelcolor::raw::RawU2;
 }
 
+/// The [`DisplayBuffer`] bit-plane a color is stored in, see
+/// [`QuadColor::plane`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Plane {
+    /// `bw`: black where a bit is 0, white where all planes are 1.
+    BlackWhite,
+    /// `red`: red where a bit is 0, overriding the other planes.
+    Red,
+    /// `yellow`: yellow where a bit is 0, overriding `bw`.
+    Yellow,
+}
+
+impl QuadColor {
+    /// Whether this is one of the accent inks (red or yellow) rather than the
+    /// black/white base layer.
+    pub const fn is_accent(self) -> bool {
+        matches!(self, QuadColor::Red | QuadColor::Yellow)
+    }
+
+    /// The bit-plane of [`DisplayBuffer`] that holds this color.
+    pub const fn plane(self) -> Plane {
+        match self {
+            QuadColor::Black | QuadColor::White => Plane::BlackWhite,
+            QuadColor::Red => Plane::Red,
+            QuadColor::Yellow => Plane::Yellow,
+        }
+    }
+
+    /// 2-bit code of this color in the controller RAM.
+    ///
+    /// Mapping corrected based on hardware observation:
+    /// 00 -> Black, 01 -> White, 10 -> Yellow, 11 -> Red
+    const fn ram_code(self) -> u8 {
+        match self {
+            QuadColor::Black => 0b00,
+            QuadColor::White => 0b01,
+            QuadColor::Yellow => 0b10,
+            QuadColor::Red => 0b11,
+        }
+    }
+
+    /// Plain numeric code for storage or FFI: the same 0..=3 codes the
+    /// controller RAM uses (0 black, 1 white, 2 yellow, 3 red), independent of
+    /// embedded-graphics' `RawU2` conversions.
+    pub const fn to_u8(self) -> u8 {
+        self.ram_code()
+    }
+
+    /// Inverse of [`to_u8`](Self::to_u8); `None` for values above 3.
+    pub const fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0b00 => Some(QuadColor::Black),
+            0b01 => Some(QuadColor::White),
+            0b10 => Some(QuadColor::Yellow),
+            0b11 => Some(QuadColor::Red),
+            _ => None,
+        }
+    }
+
+    /// The "opposite" ink, for blink and highlight effects: black and white
+    /// swap, and so do red and yellow.
+    ///
+    /// The accents are paired with each other rather than with black or white
+    /// so that complementing keeps an accent an accent and applying it twice is
+    /// the identity.
+    pub const fn complement(self) -> Self {
+        match self {
+            QuadColor::Black => QuadColor::White,
+            QuadColor::White => QuadColor::Black,
+            QuadColor::Red => QuadColor::Yellow,
+            QuadColor::Yellow => QuadColor::Red,
+        }
+    }
+
+    /// Approximates `coverage`/255 of `target` over `self` with a single ink,
+    /// e.g. for anti-aliased glyph edges.
+    ///
+    /// The inks are ranked by lightness, black < red < yellow < white, and the
+    /// result is the ink at the rounded position between the two ranks. Going
+    /// from white toward black this gives:
+    ///
+    /// | coverage  | result |
+    /// |-----------|--------|
+    /// | 0..=42    | white  |
+    /// | 43..=127  | yellow |
+    /// | 128..=212 | red    |
+    /// | 213..=255 | black  |
+    ///
+    /// Adjacent inks (e.g. red and yellow) simply switch at half coverage.
+    pub const fn blend_toward(self, target: QuadColor, coverage: u8) -> QuadColor {
+        const fn rank(color: QuadColor) -> i32 {
+            match color {
+                QuadColor::Black => 0,
+                QuadColor::Red => 1,
+                QuadColor::Yellow => 2,
+                QuadColor::White => 3,
+            }
+        }
+        let (from, to) = (rank(self), rank(target));
+        match (from * 255 + (to - from) * coverage as i32 + 127) / 255 {
+            0 => QuadColor::Black,
+            1 => QuadColor::Red,
+            2 => QuadColor::Yellow,
+            _ => QuadColor::White,
+        }
+    }
+
+    /// Maps `color` to the panel ink it looks closest to, using [`PANEL_INKS`].
+    ///
+    /// Slower than the plain `From<Rgb888>` conversion but gives noticeably
+    /// better results for photos, where the muted red and yellow inks are
+    /// otherwise over- or under-used.
+    pub fn map_perceptual(color: Rgb888) -> Self {
+        PANEL_INKS.nearest(color)
+    }
+
+    /// The pure RGB color this ink stands for, the same reference colors the
+    /// `From<Rgb888>` conversion matches against.
+    pub const fn to_rgb888(self) -> Rgb888 {
+        match self {
+            QuadColor::Black => Rgb888::BLACK,
+            QuadColor::White => Rgb888::WHITE,
+            QuadColor::Red => Rgb888::RED,
+            QuadColor::Yellow => Rgb888::YELLOW,
+        }
+    }
+
+    /// [`to_rgb888`](Self::to_rgb888) for RGB565 displays, e.g. to render the
+    /// same `QuadColor` UI on a GC9A01 LCD.
+    pub const fn to_rgb565(self) -> Rgb565 {
+        match self {
+            QuadColor::Black => Rgb565::BLACK,
+            QuadColor::White => Rgb565::WHITE,
+            QuadColor::Red => Rgb565::RED,
+            QuadColor::Yellow => Rgb565::YELLOW,
+        }
+    }
+
+    /// [`to_rgb888`](Self::to_rgb888) for BGR565 displays.
+    pub const fn to_bgr565(self) -> Bgr565 {
+        match self {
+            QuadColor::Black => Bgr565::BLACK,
+            QuadColor::White => Bgr565::WHITE,
+            QuadColor::Red => Bgr565::RED,
+            QuadColor::Yellow => Bgr565::YELLOW,
+        }
+    }
+}
+
+impl From<QuadColor> for Rgb888 {
+    fn from(color: QuadColor) -> Self {
+        color.to_rgb888()
+    }
+}
+
+impl From<QuadColor> for Rgb565 {
+    fn from(color: QuadColor) -> Self {
+        color.to_rgb565()
+    }
+}
+
+impl From<QuadColor> for Bgr565 {
+    fn from(color: QuadColor) -> Self {
+        color.to_bgr565()
+    }
+}
+
+/// White, the panel's cleared state and the background of
+/// [`DisplayBuffer::new`], so default-initialized colors draw nothing visible.
+impl Default for QuadColor {
+    fn default() -> Self {
+        QuadColor::White
+    }
+}
+
+/// Character used for this color by [`DisplayBuffer::dump_ascii`].
+impl From<QuadColor> for char {
+    fn from(color: QuadColor) -> Self {
+        match color {
+            QuadColor::Black => '#',
+            QuadColor::White => ' ',
+            QuadColor::Red => 'R',
+            QuadColor::Yellow => 'Y',
+        }
+    }
+}
+
+/// Nearest pure color by plain RGB Euclidean distance.
+impl From<Rgb888> for QuadColor {
+    fn from(color: Rgb888) -> Self {
+        let candidates = [
+            (QuadColor::Black, Rgb888::BLACK),
+            (QuadColor::White, Rgb888::WHITE),
+            (QuadColor::Red, Rgb888::RED),
+            (QuadColor::Yellow, Rgb888::YELLOW),
+        ];
+
+        let mut best = QuadColor::White;
+        let mut best_dist = u32::MAX;
+        for (quad, reference) in candidates {
+            let dr = color.r().abs_diff(reference.r()) as u32;
+            let dg = color.g().abs_diff(reference.g()) as u32;
+            let db = color.b().abs_diff(reference.b()) as u32;
+            let dist = dr * dr + dg * dg + db * db;
+            if dist < best_dist {
+                best = quad;
+                best_dist = dist;
+            }
+        }
+        best
+    }
+}
+
+/// RGB appearance of each of the panel's four inks.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct InkPalette {
+    pub black: Rgb888,
+    pub white: Rgb888,
+    pub red: Rgb888,
+    pub yellow: Rgb888,
+}
+
+/// Approximate ink colors of the 2.13" quad-color panel.
+///
+/// Panels vary between lots; build your own [`InkPalette`] from measured values if
+/// conversions look off.
+pub const PANEL_INKS: InkPalette = InkPalette {
+    black: Rgb888::new(0x1E, 0x1E, 0x23),
+    white: Rgb888::new(0xDC, 0xDC, 0xD2),
+    red: Rgb888::new(0xA5, 0x23, 0x1E),
+    yellow: Rgb888::new(0xE1, 0xC3, 0x0F),
+};
+
+impl InkPalette {
+    /// Returns the ink closest to `color` using the "redmean" weighted distance,
+    /// a cheap integer approximation of perceptual color difference.
+    pub fn nearest(&self, color: Rgb888) -> QuadColor {
+        let candidates = [
+            (QuadColor::Black, self.black),
+            (QuadColor::White, self.white),
+            (QuadColor::Red, self.red),
+            (QuadColor::Yellow, self.yellow),
+        ];
+
+        let mut best = QuadColor::White;
+        let mut best_dist = u32::MAX;
+        for (quad, ink) in candidates {
+            let rmean = (color.r() as u32 + ink.r() as u32) / 2;
+            let dr = color.r().abs_diff(ink.r()) as u32;
+            let dg = color.g().abs_diff(ink.g()) as u32;
+            let db = color.b().abs_diff(ink.b()) as u32;
+            let dist =
+                (((512 + rmean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - rmean) * db * db) >> 8);
+            if dist < best_dist {
+                best = quad;
+                best_dist = dist;
+            }
+        }
+        best
+    }
+}
+
 impl DrawTarget for DisplayBuffer {
     type Color = QuadColor;
-    type Error = core::convert::Infallible;
+    type Error = Infallible;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
         for Pixel(point, color) in pixels.into_iter() {
+            let color = self.resolve(color);
             if point.x >= 0 && point.x < WIDTH as i32 && point.y >= 0 && point.y < HEIGHT as i32 {
-                let idx = (point.y as usize * WIDTH + point.x as usize) / 8;
-                let bit = 7 - (point.x as usize % 8);
+                let i = point.y as usize * WIDTH + point.x as usize;
+                let (idx, bit) = (i / 8, 7 - i % 8);
 
                 // Clear all bits at this position first (set to 1 = White/Clear)
                 self.bw[idx] |= 1 << bit;
@@ -256,6 +2839,16 @@ impl DrawTarget for DisplayBuffer {
         }
         Ok(())
     }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_rect(*area, color);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill(color);
+        Ok(())
+    }
 }
 
 impl OriginDimensions for DisplayBuffer {
@@ -263,3 +2856,6 @@ impl OriginDimensions for DisplayBuffer {
         Size::new(WIDTH as u32, HEIGHT as u32)
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+//! Guard rail against refreshing the panel in a tight loop.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Jd79661, Jd79661Error};
+
+/// Minimum gap [`RefreshLimiter::default`] enforces between refreshes.
+pub const DEFAULT_MIN_REFRESH_INTERVAL_MS: u32 = 3_000;
+
+/// Enforces a minimum interval between full refreshes.
+///
+/// E-paper wears with every refresh cycle, and a loop that accidentally
+/// refreshes back to back can damage the panel. The limiter has no clock of
+/// its own: pass a millisecond timestamp (e.g. from a timer peripheral) to
+/// [`Jd79661::display_frame_limited`]. Timestamps may wrap around `u32`.
+#[derive(Copy, Clone, Debug)]
+pub struct RefreshLimiter {
+    min_interval_ms: u32,
+    last_ms: Option<u32>,
+}
+
+impl RefreshLimiter {
+    pub const fn new(min_interval_ms: u32) -> Self {
+        Self {
+            min_interval_ms,
+            last_ms: None,
+        }
+    }
+
+    pub const fn min_interval_ms(&self) -> u32 {
+        self.min_interval_ms
+    }
+
+    /// Milliseconds left at `now_ms` until the next refresh is allowed, or 0
+    /// if it may run now.
+    pub fn remaining_ms(&self, now_ms: u32) -> u32 {
+        match self.last_ms {
+            Some(last) => self
+                .min_interval_ms
+                .saturating_sub(now_ms.wrapping_sub(last)),
+            None => 0,
+        }
+    }
+
+    /// Notes a refresh started at `now_ms`, for refreshes made without
+    /// [`Jd79661::display_frame_limited`].
+    pub fn record(&mut self, now_ms: u32) {
+        self.last_ms = Some(now_ms);
+    }
+
+    /// Forgets the last refresh, so the next one is allowed immediately.
+    pub fn reset(&mut self) {
+        self.last_ms = None;
+    }
+}
+
+impl Default for RefreshLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_REFRESH_INTERVAL_MS)
+    }
+}
+
+impl<CS, BUSY, DC, RST> Jd79661<CS, BUSY, DC, RST>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    /// Like [`display_frame`](Self::display_frame), but refuses with
+    /// [`Jd79661Error::TooSoon`] (without touching the panel) when `limiter`
+    /// saw a refresh less than its minimum interval before `now_ms`.
+    pub fn display_frame_limited<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+        limiter: &mut RefreshLimiter,
+        now_ms: u32,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let remaining_ms = limiter.remaining_ms(now_ms);
+        if remaining_ms > 0 {
+            return Err(Jd79661Error::TooSoon { remaining_ms });
+        }
+        limiter.record(now_ms);
+        self.display_frame(spi, delay)
+    }
+}
@@ -0,0 +1,116 @@
+//! Stand-in for control lines a board doesn't wire to a GPIO.
+
+use core::convert::Infallible;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Builder, Jd79661, Jd79661Error};
+
+/// A pin that isn't there: writes do nothing and it always reads high, the
+/// idle level of both RST and BUSY.
+///
+/// Used as the `RST` type by [`Jd79661::new_no_reset`] and the `BUSY` type by
+/// [`Jd79661::new_no_busy`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for NoPin {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl InputPin for NoPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl<CS, BUSY, DC> Jd79661<CS, BUSY, DC, NoPin>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+{
+    /// Initializes a panel whose RST isn't connected to a GPIO, e.g. because
+    /// it's tied to the board's reset line.
+    ///
+    /// This assumes the panel was already reset externally (at power-up or
+    /// together with the MCU): the hardware reset pulse is skipped and init
+    /// starts straight at the software reset. [`recover`](Self::recover) and
+    /// the retry after a stuck BUSY line therefore only get a software reset.
+    /// For other settings, pass [`NoPin`] to [`Builder::init`] with
+    /// `reset_pulse(0, 0)`.
+    pub fn new_no_reset<SPI, DELAY>(
+        spi: &mut SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        delay: &mut DELAY,
+    ) -> Result<Self, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+    {
+        Builder::new()
+            .reset_pulse(0, 0)
+            .init(spi, cs, busy, dc, NoPin, delay)
+    }
+}
+
+impl<CS, DC, RST> Jd79661<CS, NoPin, DC, RST>
+where
+    CS: OutputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    /// Initializes a panel whose BUSY line isn't connected (or floats), waiting
+    /// fixed times instead of polling it.
+    ///
+    /// Every operation then takes its worst case: about 1 s for reset and
+    /// switching the charge pumps, 20 s for a full refresh and 5 s for a
+    /// partial one, even when the panel finishes sooner. A slow panel that
+    /// needs longer is not detected and may get commands mid-refresh, and
+    /// [`test_connectivity`](Self::test_connectivity) always reports
+    /// [`Jd79661Error::NotResponding`].
+    pub fn new_no_busy<SPI, DELAY>(
+        spi: &mut SPI,
+        cs: CS,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, Jd79661Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        DELAY: DelayNs,
+    {
+        let mut driver = Builder::new().build(cs, NoPin, dc, rst);
+        driver.busy_wired = false;
+        driver.ensure_initialized(spi, delay)?;
+        Ok(driver)
+    }
+}
@@ -0,0 +1,88 @@
+//! A [`Jd79661`] that owns its SPI device, in the style of `ssd1681`/`mipidsi`.
+
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Builder, DisplayBuffer, Jd79661, Jd79661Error};
+
+/// [`Jd79661`] bundled with the `SpiDevice` it talks through, so methods don't
+/// need `spi` threaded through every call.
+///
+/// Use the plain [`Jd79661`] when the bus is shared with other devices and
+/// borrowed per call.
+pub struct Jd79661Owned<SPI, CS, BUSY, DC, RST> {
+    spi: SPI,
+    driver: Jd79661<CS, BUSY, DC, RST>,
+}
+
+impl<SPI, CS, BUSY, DC, RST> Jd79661Owned<SPI, CS, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    pub fn new<DELAY: DelayNs>(
+        mut spi: SPI,
+        cs: CS,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, Jd79661Error<SPI::Error>> {
+        let driver = Builder::new().init(&mut spi, cs, busy, dc, rst, delay)?;
+        Ok(Self::from_parts(spi, driver))
+    }
+
+    /// Wraps an already initialized driver together with its SPI device, e.g.
+    /// one set up through a [`Builder`].
+    pub fn from_parts(spi: SPI, driver: Jd79661<CS, BUSY, DC, RST>) -> Self {
+        Self { spi, driver }
+    }
+
+    /// Gives back the SPI device and the borrow-per-call driver.
+    pub fn release(self) -> (SPI, Jd79661<CS, BUSY, DC, RST>) {
+        (self.spi, self.driver)
+    }
+
+    /// Borrows the driver and SPI device separately, for calling any
+    /// [`Jd79661`] method not mirrored here.
+    pub fn parts(&mut self) -> (&mut Jd79661<CS, BUSY, DC, RST>, &mut SPI) {
+        (&mut self.driver, &mut self.spi)
+    }
+
+    pub fn update_frames(
+        &mut self,
+        display: &DisplayBuffer,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        self.driver.update_frames(&mut self.spi, display)
+    }
+
+    /// Refreshes the panel from its RAM, like [`Jd79661::display_frame`].
+    pub fn refresh<DELAY: DelayNs>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.driver.display_frame(&mut self.spi, delay)
+    }
+
+    pub fn update_partial<DELAY: DelayNs>(
+        &mut self,
+        display: &DisplayBuffer,
+        area: Rectangle,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.driver
+            .update_partial(&mut self.spi, display, area, delay)
+    }
+
+    pub fn soft_reset<DELAY: DelayNs>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.driver.soft_reset(&mut self.spi, delay)
+    }
+}
@@ -0,0 +1,80 @@
+//! Index-to-ink lookup for palette-indexed content.
+
+use embedded_graphics::prelude::*;
+
+use crate::{BufferError, DisplayBuffer, QuadColor};
+
+/// Maps every possible index byte to a [`QuadColor`], for drawing
+/// palette-indexed images (GIF-style frames, tile maps) with
+/// [`DisplayBuffer::draw_indexed`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Palette {
+    colors: [QuadColor; 256],
+}
+
+impl Palette {
+    /// A palette mapping every index to `color`; set the used entries with
+    /// [`set`](Self::set).
+    pub const fn new(color: QuadColor) -> Self {
+        Self {
+            colors: [color; 256],
+        }
+    }
+
+    /// Maps index `i` to `colors[i]` and every index past the end of `colors`
+    /// to white.
+    pub const fn from_colors(colors: &[QuadColor]) -> Self {
+        let mut palette = Self::new(QuadColor::White);
+        let mut i = 0;
+        while i < colors.len() && i < 256 {
+            palette.colors[i] = colors[i];
+            i += 1;
+        }
+        palette
+    }
+
+    pub const fn set(&mut self, index: u8, color: QuadColor) {
+        self.colors[index as usize] = color;
+    }
+
+    pub const fn get(&self, index: u8) -> QuadColor {
+        self.colors[index as usize]
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(QuadColor::White)
+    }
+}
+
+impl DisplayBuffer {
+    /// Draws a row-major image of one index byte per pixel, `width` pixels
+    /// wide, with its top-left corner at `origin`, looking each index up in
+    /// `palette`. Parts outside the buffer are clipped.
+    ///
+    /// The height follows from the length, which must be a whole number of
+    /// rows.
+    pub fn draw_indexed(
+        &mut self,
+        indices: &[u8],
+        width: usize,
+        origin: Point,
+        palette: &Palette,
+    ) -> Result<(), BufferError> {
+        let rows = indices.len().checked_div(width).unwrap_or(0);
+        if rows * width != indices.len() {
+            return Err(BufferError::Length {
+                expected: (rows + 1) * width,
+                actual: indices.len(),
+            });
+        }
+
+        let pixels = indices.iter().enumerate().map(|(i, &index)| {
+            let point = Point::new((i % width) as i32, (i / width) as i32);
+            Pixel(origin + point, palette.get(index))
+        });
+        let _ = self.draw_iter(pixels);
+        Ok(())
+    }
+}
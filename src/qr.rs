@@ -0,0 +1,67 @@
+//! QR code rendering for the quad-color panel (`qr` feature).
+//!
+//! Wraps the `no_std` [`qrcodegen_no_heap`] encoder and draws dark modules as
+//! [`QuadColor::Black`] on white, which is what phone cameras read best.
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use qrcodegen_no_heap::{QrCode, QrCodeEcc, Version};
+
+pub use qrcodegen_no_heap::DataTooLong;
+
+use crate::{DisplayBuffer, QuadColor};
+
+/// Largest QR version whose 121 modules still fit the panel height at one pixel
+/// per module.
+const MAX_VERSION: Version = Version::new(26);
+
+impl DisplayBuffer {
+    /// Renders `text` as a QR code whose top-left corner (including the quiet
+    /// zone) is at `origin`.
+    ///
+    /// Each module is drawn as a `module_size` pixel square and the code is
+    /// surrounded by `quiet_zone` white modules; the QR spec asks for 4, but 2 is
+    /// usually enough against the panel's white background. Returns the area
+    /// that was drawn. Modules falling outside the buffer are clipped.
+    pub fn draw_qr(
+        &mut self,
+        text: &str,
+        origin: Point,
+        module_size: u32,
+        quiet_zone: u32,
+    ) -> Result<Rectangle, DataTooLong> {
+        let mut out = [0u8; MAX_VERSION.buffer_len()];
+        let mut temp = [0u8; MAX_VERSION.buffer_len()];
+        let qr = QrCode::encode_text(
+            text,
+            &mut temp,
+            &mut out,
+            QrCodeEcc::Medium,
+            Version::MIN,
+            MAX_VERSION,
+            None,
+            true,
+        )?;
+
+        let modules = qr.size() as u32 + 2 * quiet_zone;
+        let area = Rectangle::new(origin, Size::new_equal(modules * module_size));
+        let _ = self.fill_solid(&area, QuadColor::White);
+
+        let module = Size::new_equal(module_size);
+        let offset = (quiet_zone * module_size) as i32;
+        for y in 0..qr.size() {
+            for x in 0..qr.size() {
+                if qr.get_module(x, y) {
+                    let top_left = origin
+                        + Point::new(
+                            offset + x * module_size as i32,
+                            offset + y * module_size as i32,
+                        );
+                    let _ = self.fill_solid(&Rectangle::new(top_left, module), QuadColor::Black);
+                }
+            }
+        }
+
+        Ok(area)
+    }
+}
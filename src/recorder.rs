@@ -0,0 +1,72 @@
+//! Recording draw operations once and stamping them onto many buffers.
+
+use embedded_graphics::prelude::*;
+use heapless::Vec;
+
+use crate::{DisplayBuffer, QuadColor, HEIGHT, WIDTH};
+
+/// Returned when a [`DrawRecorder`] has no room left for more pixels.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct RecorderFull;
+
+/// A `DrawTarget` that records up to `N` pixels instead of rendering them, so a
+/// scene or sprite can be built once and [`replay`](Self::replay)ed onto any
+/// number of frames.
+///
+/// Each recorded pixel takes 12 bytes, so size `N` for the scene at hand
+/// rather than the whole panel. Pixels outside the panel aren't recorded.
+#[derive(Clone, Debug)]
+pub struct DrawRecorder<const N: usize> {
+    pixels: Vec<Pixel<QuadColor>, N>,
+}
+
+impl<const N: usize> DrawRecorder<N> {
+    pub const fn new() -> Self {
+        Self { pixels: Vec::new() }
+    }
+
+    /// Draws the recorded pixels onto `target`, in the order they were drawn.
+    pub fn replay(&self, target: &mut DisplayBuffer) {
+        let _ = target.draw_iter(self.pixels.iter().copied());
+    }
+
+    /// Recorded pixels, in drawing order.
+    pub fn pixels(&self) -> &[Pixel<QuadColor>] {
+        &self.pixels
+    }
+
+    /// Forgets all recorded pixels.
+    pub fn clear(&mut self) {
+        self.pixels.clear();
+    }
+}
+
+impl<const N: usize> Default for DrawRecorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DrawTarget for DrawRecorder<N> {
+    type Color = QuadColor;
+    type Error = RecorderFull;
+
+    /// Records the pixels that lie on the panel. Once the recorder is full,
+    /// the remaining pixels are dropped and [`RecorderFull`] is returned.
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.bounding_box();
+        for pixel in pixels.into_iter().filter(|Pixel(p, _)| area.contains(*p)) {
+            self.pixels.push(pixel).map_err(|_| RecorderFull)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> OriginDimensions for DrawRecorder<N> {
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
@@ -0,0 +1,83 @@
+//! Read-only per-scanline access to a [`DisplayBuffer`], for streaming frames
+//! row by row without copying the whole buffer.
+
+use crate::{DisplayBuffer, Plane, QuadColor, BUF_SIZE, HEIGHT, WIDTH};
+
+/// Bytes in [`RowView::packed`]: 250 pixels at 2 bits, the last byte holding
+/// two pixels.
+pub const ROW_PACKED_BYTES: usize = WIDTH.div_ceil(4);
+
+/// Bytes in [`RowView::plane`]: 250 pixels at 1 bit, the last byte holding
+/// two pixels.
+pub const ROW_PLANE_BYTES: usize = WIDTH.div_ceil(8);
+
+/// One scanline of a [`DisplayBuffer`], see [`DisplayBuffer::rows`].
+#[derive(Copy, Clone, Debug)]
+pub struct RowView<'a> {
+    buffer: &'a DisplayBuffer,
+    y: usize,
+}
+
+impl RowView<'_> {
+    /// The row's y coordinate, 0 at the top.
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// The color at column `x`, or `None` past the right edge.
+    pub fn get(&self, x: usize) -> Option<QuadColor> {
+        self.buffer.get_pixel(x, self.y)
+    }
+
+    /// The row as 2-bit color codes ([`QuadColor::to_u8`]), four pixels per
+    /// byte with the leftmost in the top bits and zero padding at the end;
+    /// the packing [`DisplayBuffer::region_hash`] and
+    /// [`DisplayBuffer::draw_bitmap_packed`] use.
+    pub fn packed(&self) -> [u8; ROW_PACKED_BYTES] {
+        let mut out = [0u8; ROW_PACKED_BYTES];
+        for x in 0..WIDTH {
+            let code = self.buffer.color_at(x, self.y).to_u8();
+            out[x / 4] |= code << (6 - 2 * (x % 4));
+        }
+        out
+    }
+
+    /// The row's bits of one plane, realigned to start on a byte: MSB first,
+    /// a 0 bit is ink as in the full planes, and the six padding bits at the
+    /// end are 1.
+    ///
+    /// Rows in the planes don't start on byte boundaries (250 isn't a multiple
+    /// of 8), so this is a shifted copy rather than a slice.
+    pub fn plane(&self, plane: Plane) -> [u8; ROW_PLANE_BYTES] {
+        let bits = match plane {
+            Plane::BlackWhite => &self.buffer.bw,
+            Plane::Red => &self.buffer.red,
+            Plane::Yellow => &self.buffer.yellow,
+        };
+        let start = self.y * WIDTH;
+        let (first, shift) = (start / 8, start % 8);
+
+        let mut out = [0xFFu8; ROW_PLANE_BYTES];
+        for (k, byte) in out.iter_mut().enumerate() {
+            let hi = bits[first + k] << shift;
+            let lo = match bits.get(first + k + 1) {
+                Some(next) if shift != 0 => next >> (8 - shift),
+                _ => 0,
+            };
+            *byte = hi | lo;
+        }
+        out[ROW_PLANE_BYTES - 1] |= 0xFF >> (WIDTH % 8);
+        out
+    }
+}
+
+impl DisplayBuffer {
+    /// The buffer's [`HEIGHT`] scanlines from the top, e.g. to send a frame to
+    /// a co-processor or compress it one row at a time.
+    pub fn rows(&self) -> impl Iterator<Item = RowView<'_>> {
+        (0..HEIGHT).map(move |y| RowView { buffer: self, y })
+    }
+}
+
+// Every row's realigned plane bytes lie inside the buffer.
+const _: () = assert!((HEIGHT - 1) * WIDTH / 8 + ROW_PLANE_BYTES <= BUF_SIZE);
@@ -0,0 +1,753 @@
+//! Host unit tests, run with `cargo test --lib --target x86_64-unknown-linux-gnu`.
+
+use core::cell::Cell;
+use core::convert::Infallible;
+
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_hal::digital::ErrorType;
+use embedded_hal::spi::Operation;
+
+use super::*;
+
+/// Accepts every transfer. Once `jam` is set, the next transfer leaves BUSY
+/// stuck asserted, like a panel hanging on the command it received.
+struct MockSpi<'a> {
+    busy: &'a MockBusy,
+    jam: bool,
+}
+
+impl embedded_hal::spi::ErrorType for MockSpi<'_> {
+    type Error = Infallible;
+}
+
+impl SpiDevice for MockSpi<'_> {
+    fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        if self.jam {
+            self.busy.stuck.set(true);
+        }
+        Ok(())
+    }
+}
+
+/// Returns immediately, so timeouts expire without real waiting.
+struct MockDelay;
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, _: u32) {}
+}
+
+/// BUSY line that reads busy (low) while `stuck` is set, and fails every
+/// read while `broken` is set.
+#[derive(Default)]
+struct MockBusy {
+    stuck: Cell<bool>,
+    broken: Cell<bool>,
+}
+
+impl ErrorType for &MockBusy {
+    type Error = PinFault;
+}
+
+impl InputPin for &MockBusy {
+    fn is_high(&mut self) -> Result<bool, PinFault> {
+        Ok(!self.is_low()?)
+    }
+
+    fn is_low(&mut self) -> Result<bool, PinFault> {
+        if self.broken.get() {
+            return Err(PinFault);
+        }
+        Ok(self.stuck.get())
+    }
+}
+
+/// Output pin that accepts every write.
+struct MockPin;
+
+impl ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for MockPin {
+    fn is_set_high(&mut self) -> Result<bool, Infallible> {
+        Ok(true)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Infallible> {
+        Ok(false)
+    }
+}
+
+/// Pin error type that differs from the other pins' `Infallible`.
+#[derive(Debug)]
+struct PinFault;
+
+impl embedded_hal::digital::Error for PinFault {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+/// Output pin whose every write fails, e.g. an unplugged I/O expander.
+struct FailingPin;
+
+impl ErrorType for FailingPin {
+    type Error = PinFault;
+}
+
+impl OutputPin for FailingPin {
+    fn set_low(&mut self) -> Result<(), PinFault> {
+        Err(PinFault)
+    }
+
+    fn set_high(&mut self) -> Result<(), PinFault> {
+        Err(PinFault)
+    }
+}
+
+impl StatefulOutputPin for FailingPin {
+    fn is_set_high(&mut self) -> Result<bool, PinFault> {
+        Err(PinFault)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, PinFault> {
+        Err(PinFault)
+    }
+}
+
+/// Whether plane bit `(x, y)` holds ink (a cleared bit).
+fn ink_at(plane: &[u8; BUF_SIZE], x: usize, y: usize) -> bool {
+    let i = y * WIDTH + x;
+    plane[i / 8] & (1 << (7 - i % 8)) == 0
+}
+
+#[test]
+fn rotate_180_moves_corner_and_round_trips() {
+    for color in [QuadColor::Black, QuadColor::Red, QuadColor::Yellow] {
+        let mut display = DisplayBuffer::new();
+        Pixel(Point::zero(), color).draw(&mut display).unwrap();
+
+        display.rotate_180();
+        assert_eq!(display.get_pixel(0, 0), Some(QuadColor::White));
+        for (plane, ink) in [
+            (&display.bw, QuadColor::Black),
+            (&display.red, QuadColor::Red),
+            (&display.yellow, QuadColor::Yellow),
+        ] {
+            assert_eq!(ink_at(plane, WIDTH - 1, HEIGHT - 1), ink == color);
+            assert!(!ink_at(plane, 0, 0));
+        }
+    }
+
+    let mut display = DisplayBuffer::new();
+    for (i, color) in [QuadColor::Black, QuadColor::Red, QuadColor::Yellow]
+        .into_iter()
+        .enumerate()
+    {
+        Rectangle::new(Point::new(3 + 40 * i as i32, 5), Size::new(17, 9))
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(&mut display)
+            .unwrap();
+    }
+    let original = (display.bw, display.red, display.yellow);
+    display.rotate_180();
+    assert_ne!((display.bw, display.red, display.yellow), original);
+    display.rotate_180();
+    assert_eq!((display.bw, display.red, display.yellow), original);
+}
+
+#[test]
+fn display_frame_times_out_on_stuck_busy() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init(&mut spi, MockPin, &busy, MockPin, MockPin, &mut MockDelay)
+        .unwrap();
+
+    spi.jam = true;
+    let result = epd.display_frame(&mut spi, &mut MockDelay);
+    assert!(matches!(result, Err(Jd79661Error::BusyTimeout)));
+}
+
+#[test]
+fn power_on_times_out_on_stuck_busy() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init_unpowered(&mut spi, MockPin, &busy, MockPin, MockPin, &mut MockDelay)
+        .unwrap();
+
+    spi.jam = true;
+    let result = epd.power_on(&mut spi, &mut MockDelay);
+    assert!(matches!(result, Err(Jd79661Error::BusyTimeout)));
+    assert_eq!(epd.power_state(), PowerState::Off);
+}
+
+#[test]
+fn init_fails_on_stuck_busy() {
+    // Init retries once after a hardware reset, then reports the timeout as
+    // InitFailed rather than hanging.
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: true,
+    };
+    let result = Builder::new().init(&mut spi, MockPin, &busy, MockPin, MockPin, &mut MockDelay);
+    assert!(matches!(result, Err(Jd79661Error::InitFailed)));
+}
+
+#[test]
+fn color_codes_round_trip_and_match_ram() {
+    for (color, code) in [
+        (QuadColor::Black, 0b00),
+        (QuadColor::White, 0b01),
+        (QuadColor::Yellow, 0b10),
+        (QuadColor::Red, 0b11),
+    ] {
+        assert_eq!(color.to_u8(), code);
+        assert_eq!(color.to_u8(), color.ram_code());
+        assert_eq!(QuadColor::from_u8(color.to_u8()), Some(color));
+    }
+    assert_eq!(QuadColor::from_u8(4), None);
+    assert_eq!(QuadColor::from_u8(255), None);
+}
+
+#[test]
+fn map_colors_only_changes_matching_pixels() {
+    let mut display = DisplayBuffer::new();
+    for (i, color) in [QuadColor::Black, QuadColor::Red, QuadColor::Yellow]
+        .into_iter()
+        .enumerate()
+    {
+        Rectangle::new(
+            Point::new(60 * i as i32 + 1, 0),
+            Size::new(60, HEIGHT as u32),
+        )
+        .into_styled(PrimitiveStyle::with_fill(color))
+        .draw(&mut display)
+        .unwrap();
+    }
+    // Red ink on top of yellow ink: red wins when decoding, so the pixel
+    // shows red and must be mapped too.
+    let (x, y) = (200, 50);
+    let i = y * WIDTH + x;
+    display.red[i / 8] &= !(1 << (7 - i % 8));
+    display.yellow[i / 8] &= !(1 << (7 - i % 8));
+    assert_eq!(display.get_pixel(x, y), Some(QuadColor::Red));
+
+    let before = display.clone();
+    display.map_colors(QuadColor::Red, QuadColor::Yellow);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let expected = match before.get_pixel(x, y) {
+                Some(QuadColor::Red) => Some(QuadColor::Yellow),
+                other => other,
+            };
+            assert_eq!(display.get_pixel(x, y), expected, "pixel ({x}, {y})");
+        }
+    }
+    assert!(!ink_at(&display.red, x, y));
+    assert!(ink_at(&display.yellow, x, y));
+    assert_eq!(display.get_pixel(0, 0), Some(QuadColor::White));
+    assert_eq!(display.bw, before.bw);
+}
+
+#[test]
+fn init_reports_failing_reset_pin() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let result = Builder::new().init(&mut spi, NoPin, &busy, NoPin, FailingPin, &mut MockDelay);
+    let err = result.err().unwrap();
+    assert_eq!(err.as_pin(), Some(embedded_hal::digital::ErrorKind::Other));
+}
+
+#[test]
+fn failing_busy_read_is_not_idle() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &busy, NoPin, NoPin, &mut MockDelay)
+        .unwrap();
+    busy.broken.set(true);
+
+    let kind = Some(embedded_hal::digital::ErrorKind::Other);
+    assert_eq!(epd.is_busy::<Infallible>().err().unwrap().as_pin(), kind);
+    assert_eq!(epd.busy_high::<Infallible>().err().unwrap().as_pin(), kind);
+    assert_eq!(
+        epd.refresh_done::<Infallible>().err().unwrap().as_pin(),
+        kind
+    );
+    let result = epd.try_update_frames(&mut spi, &DisplayBuffer::new());
+    assert_eq!(result.err().unwrap().as_pin(), kind);
+}
+
+#[test]
+fn update_frames_if_changed_skips_identical_frames() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &busy, NoPin, NoPin, &mut MockDelay)
+        .unwrap();
+    let mut display = DisplayBuffer::new();
+
+    assert!(epd
+        .update_frames_if_changed(&mut spi, &display, false)
+        .unwrap());
+    assert_eq!(epd.current_frame_crc(), Some(display.frame_crc()));
+    assert!(!epd
+        .update_frames_if_changed(&mut spi, &display, false)
+        .unwrap());
+
+    Pixel(Point::new(10, 20), QuadColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    assert!(epd
+        .update_frames_if_changed(&mut spi, &display, false)
+        .unwrap());
+    assert!(epd
+        .update_frames_if_changed(&mut spi, &display, true)
+        .unwrap());
+}
+
+/// DC line and SPI bus in one, noting the last command byte sent and the
+/// payload of the last Partial Window (0x90) command. Sending `jam_on` leaves
+/// `busy` stuck.
+#[derive(Default)]
+struct CommandProbe {
+    data_mode: Cell<bool>,
+    last_command: Cell<Option<u8>>,
+    window: Cell<Option<[u8; 9]>>,
+    busy: MockBusy,
+    jam_on: Cell<Option<u8>>,
+}
+
+impl ErrorType for &CommandProbe {
+    type Error = Infallible;
+}
+
+impl OutputPin for &CommandProbe {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.data_mode.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.data_mode.set(true);
+        Ok(())
+    }
+}
+
+impl embedded_hal::spi::ErrorType for &CommandProbe {
+    type Error = Infallible;
+}
+
+impl SpiDevice for &CommandProbe {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        for op in operations {
+            let Operation::Write(bytes) = op else {
+                continue;
+            };
+            if !self.data_mode.get() {
+                self.last_command.set(bytes.last().copied());
+                if self.last_command.get() == self.jam_on.get() {
+                    self.busy.stuck.set(true);
+                }
+            } else if self.last_command.get() == Some(0x90) {
+                self.window.set((*bytes).try_into().ok());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn update_accent_retries_after_not_powered() {
+    let probe = CommandProbe::default();
+    let mut spi = &probe;
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &probe.busy, &probe, NoPin, &mut MockDelay)
+        .unwrap();
+    let mut display = DisplayBuffer::new();
+    let blank = display.red;
+    let mut banner = blank;
+    banner[..4].fill(0);
+
+    epd.sleep(&mut spi, &mut MockDelay).unwrap();
+    let result = epd.update_accent(
+        &mut spi,
+        &mut display,
+        QuadColor::Red,
+        &banner,
+        &mut MockDelay,
+    );
+    assert!(matches!(result, Err(Jd79661Error::NotPowered)));
+    assert_eq!(display.red, blank);
+
+    epd.wake(&mut spi, &mut MockDelay).unwrap();
+    probe.window.set(None);
+    epd.update_accent(
+        &mut spi,
+        &mut display,
+        QuadColor::Red,
+        &banner,
+        &mut MockDelay,
+    )
+    .unwrap();
+    assert!(probe.window.get().is_some());
+    assert_eq!(display.red, banner);
+}
+
+#[test]
+fn update_partial_xywh_rounds_rows_to_ram_bytes() {
+    let probe = CommandProbe::default();
+    let mut spi = &probe;
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &probe.busy, &probe, NoPin, &mut MockDelay)
+        .unwrap();
+    let display = DisplayBuffer::new();
+
+    // Rows 5..8 sit on sources 114..117, widened to the bytes 112..120;
+    // columns 10..30 are gates 10..=29 as they are.
+    epd.update_partial_xywh(&mut spi, &display, 10, 5, 20, 3, &mut MockDelay)
+        .unwrap();
+    assert_eq!(
+        probe.window.get(),
+        Some([0, 112, 0, 119, 0, 10, 0, 29, 0x01])
+    );
+    assert_eq!(probe.last_command.get(), Some(0x92));
+}
+
+#[test]
+fn refresh_partial_leaves_partial_mode_after_timeout() {
+    let probe = CommandProbe::default();
+    let mut spi = &probe;
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &probe.busy, &probe, NoPin, &mut MockDelay)
+        .unwrap();
+    let display = DisplayBuffer::new();
+
+    probe.jam_on.set(Some(0x12));
+    let result = epd.update_partial_xywh(&mut spi, &display, 0, 0, 8, 8, &mut MockDelay);
+    assert!(matches!(result, Err(Jd79661Error::BusyTimeout)));
+    assert_eq!(probe.last_command.get(), Some(0x92));
+}
+
+#[test]
+fn seven_segment_lights_ascii_glyphs() {
+    // 20x40: segments 4 px thick, the middle one on rows 18..22.
+    let (top, upper_right, middle) = ((10, 1), (18, 10), (10, 20));
+    let lit = |glyph: u8| {
+        let mut display = DisplayBuffer::new();
+        display.draw_seven_segment(glyph, Point::zero(), Size::new(20, 40), QuadColor::Black);
+        [top, upper_right, middle].map(|(x, y)| display.get_pixel(x, y) == Some(QuadColor::Black))
+    };
+
+    assert_eq!(lit(b'1'), [false, true, false]);
+    assert_eq!(lit(b'7'), [true, true, false]);
+    assert_eq!(lit(b'-'), [false, false, true]);
+    assert_eq!(lit(b' '), [false; 3]);
+    assert_eq!(lit(7), [false; 3]);
+}
+
+#[test]
+fn refresh_done_needs_busy_line() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Jd79661::new_no_busy(&mut spi, NoPin, NoPin, NoPin, &mut MockDelay).unwrap();
+
+    epd.start_refresh(&mut spi).unwrap();
+    assert!(matches!(
+        epd.refresh_done::<Infallible>(),
+        Err(Jd79661Error::NoBusyLine)
+    ));
+}
+
+#[test]
+fn tiled_display_splits_drawing_at_the_seam() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let panels = core::array::from_fn(|_| {
+        Builder::new()
+            .init(&mut spi, NoPin, &busy, NoPin, NoPin, &mut MockDelay)
+            .unwrap()
+    });
+    let mut wall = TiledDisplay::<_, _, _, _, 2>::horizontal(panels);
+    assert_eq!(wall.size(), Size::new(2 * WIDTH as u32, HEIGHT as u32));
+
+    let seam = WIDTH as i32;
+    Rectangle::new(Point::new(seam - 2, 10), Size::new(4, 1))
+        .into_styled(PrimitiveStyle::with_fill(QuadColor::Red))
+        .draw(&mut wall)
+        .unwrap();
+    Pixel(Point::new(seam + 5, 20), QuadColor::Yellow)
+        .draw(&mut wall)
+        .unwrap();
+
+    let (left, right) = (wall.buffer(0), wall.buffer(1));
+    for x in WIDTH - 2..WIDTH {
+        assert!(ink_at(&left.red, x, 10));
+    }
+    for x in 0..2 {
+        assert!(ink_at(&right.red, x, 10));
+    }
+    assert!(!ink_at(&right.red, 2, 10));
+    assert!(!ink_at(&left.red, WIDTH - 3, 10));
+    assert!(ink_at(&right.yellow, 5, 20));
+    assert_eq!(left.yellow, DisplayBuffer::new().yellow);
+}
+
+#[test]
+fn mirrors_move_corner_and_round_trip() {
+    let mirrors = [
+        (
+            DisplayBuffer::mirror_x as fn(&mut DisplayBuffer),
+            (WIDTH - 1, 0),
+        ),
+        (DisplayBuffer::mirror_y, (0, HEIGHT - 1)),
+    ];
+    for (mirror, (x, y)) in mirrors {
+        for color in [QuadColor::Black, QuadColor::Red, QuadColor::Yellow] {
+            let mut display = DisplayBuffer::new();
+            Pixel(Point::zero(), color).draw(&mut display).unwrap();
+
+            mirror(&mut display);
+            assert_eq!(display.get_pixel(0, 0), Some(QuadColor::White));
+            assert_eq!(display.get_pixel(x, y), Some(color));
+        }
+
+        let mut display = DisplayBuffer::new();
+        for (i, color) in [QuadColor::Black, QuadColor::Red, QuadColor::Yellow]
+            .into_iter()
+            .enumerate()
+        {
+            Rectangle::new(Point::new(3 + 40 * i as i32, 5), Size::new(17, 9))
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(&mut display)
+                .unwrap();
+        }
+        let original = (display.bw, display.red, display.yellow);
+        mirror(&mut display);
+        assert_ne!((display.bw, display.red, display.yellow), original);
+        mirror(&mut display);
+        assert_eq!((display.bw, display.red, display.yellow), original);
+    }
+}
+
+#[test]
+fn diff_bounds_covers_changes_in_every_plane() {
+    let blank = DisplayBuffer::new();
+    let mut display = DisplayBuffer::new();
+    assert_eq!(display.diff_bounds(&blank), None);
+
+    Pixel(Point::new(7, 9), QuadColor::Black)
+        .draw(&mut display)
+        .unwrap();
+    assert_eq!(
+        display.diff_bounds(&blank),
+        Some(Rectangle::new(Point::new(7, 9), Size::new(1, 1)))
+    );
+
+    Pixel(Point::new(20, 30), QuadColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    let bounds = Rectangle::new(Point::new(7, 9), Size::new(14, 22));
+    assert_eq!(display.diff_bounds(&blank), Some(bounds));
+    assert_eq!(blank.diff_bounds(&display), Some(bounds));
+}
+
+#[test]
+fn region_hash_tracks_pixels_inside_the_region() {
+    let mut display = DisplayBuffer::new();
+    let region = Rectangle::new(Point::zero(), Size::new(5, 2));
+    // Two rows of five white pixels, each packed as 0x55 0x40 (padded).
+    assert_eq!(display.region_hash(region), 0x1c1d_45cd);
+    assert_eq!(display.region_hash(Rectangle::zero()), 0);
+
+    Pixel(Point::new(10, 10), QuadColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    assert_eq!(display.region_hash(region), 0x1c1d_45cd);
+
+    Pixel(Point::zero(), QuadColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    assert_eq!(display.region_hash(region), 0xf144_f3f6);
+}
+
+#[test]
+fn crc_matches_zlib_and_rejects_a_changed_pixel() {
+    let mut display = DisplayBuffer::new();
+    // zlib.crc32 of 122 rows of 62 bytes 0x55 and a padded 0x50.
+    let white = 0x0b6e_cb99;
+    assert_eq!(display.crc(), white);
+    assert!(display.verify_crc(white));
+
+    Pixel(
+        Point::new(WIDTH as i32 - 1, HEIGHT as i32 - 1),
+        QuadColor::Black,
+    )
+    .draw(&mut display)
+    .unwrap();
+    assert!(!display.verify_crc(white));
+}
+
+#[test]
+fn set_resolution_compacts_each_gate_line() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &busy, NoPin, NoPin, &mut MockDelay)
+        .unwrap();
+    for (width, height) in [(0, 100), (251, 100), (200, 0), (200, 130), (200, 6)] {
+        assert!(matches!(
+            epd.set_resolution(&mut spi, width, height),
+            Err(Jd79661Error::InvalidResolution { .. })
+        ));
+    }
+
+    epd.set_resolution(&mut spi, 200, 100).unwrap();
+    let original: [u8; RAM_BYTES] = core::array::from_fn(|i| (i % 251) as u8);
+    let mut frame = original;
+    assert_eq!(epd.compact_frame(&mut frame), 200 * 25);
+    for gate in 0..200 {
+        let line = gate * RAM_ROW_BYTES;
+        assert_eq!(frame[gate * 25..][..25], original[line..][..25]);
+    }
+    assert_eq!(
+        epd.update_frames(&mut spi, &DisplayBuffer::new()).unwrap(),
+        200 * 25
+    );
+}
+
+#[test]
+fn draw_bitmap_packed_reads_codes_msb_first() {
+    let mut display = DisplayBuffer::new();
+    // 5 px wide, so rows take 2 bytes: black, white, yellow, red | red, then
+    // white x4 | black.
+    let data = [0b0001_1011, 0b1100_0000, 0b0101_0101, 0b0000_0000];
+    display
+        .draw_bitmap_packed(&data, 5, Point::new(10, 20))
+        .unwrap();
+
+    use QuadColor::*;
+    let row = |y| [10, 11, 12, 13, 14].map(|x| display.get_pixel(x, y).unwrap());
+    assert_eq!(row(20), [Black, White, Yellow, Red, Red]);
+    assert_eq!(row(21), [White, White, White, White, Black]);
+    assert_eq!(display.get_pixel(15, 20), Some(White));
+
+    assert_eq!(
+        display.draw_bitmap_packed(&data[..3], 5, Point::zero()),
+        Err(BufferError::Length {
+            expected: 4,
+            actual: 3
+        })
+    );
+    let edge = Point::new(WIDTH as i32 - 2, HEIGHT as i32 - 1);
+    display.draw_bitmap_packed(&data, 5, edge).unwrap();
+    assert_eq!(display.get_pixel(WIDTH - 1, HEIGHT - 1), Some(White));
+    assert_eq!(display.get_pixel(WIDTH - 2, HEIGHT - 1), Some(Black));
+}
+
+#[test]
+fn update_from_iter_rejects_short_and_long_frames() {
+    let busy = MockBusy::default();
+    let mut spi = MockSpi {
+        busy: &busy,
+        jam: false,
+    };
+    let mut epd = Builder::new()
+        .init(&mut spi, NoPin, &busy, NoPin, NoPin, &mut MockDelay)
+        .unwrap();
+
+    for len in [RAM_BYTES - 1, RAM_BYTES + 3] {
+        let result = epd.update_from_iter(&mut spi, core::iter::repeat_n(0x55, len));
+        assert!(matches!(
+            result,
+            Err(Jd79661Error::FrameLength { expected: RAM_BYTES, actual }) if actual == len
+        ));
+    }
+    epd.update_from_iter(&mut spi, core::iter::repeat_n(0x55, RAM_BYTES))
+        .unwrap();
+}
+
+#[test]
+fn row_plane_realigns_unaligned_rows() {
+    let mut display = DisplayBuffer::new();
+    // Row 1 starts 2 bits into a plane byte.
+    for x in [0, 9, WIDTH as i32 - 1] {
+        Pixel(Point::new(x, 1), QuadColor::Red)
+            .draw(&mut display)
+            .unwrap();
+    }
+
+    let row = display.rows().nth(1).unwrap();
+    let red = row.plane(Plane::Red);
+    assert_eq!(red[0], 0b0111_1111);
+    assert_eq!(red[1], 0b1011_1111);
+    // x = 249 is the second bit of the last byte; the rest is padding.
+    assert_eq!(red[ROW_PLANE_BYTES - 1], 0b1011_1111);
+    assert!(red[2..ROW_PLANE_BYTES - 1].iter().all(|&b| b == 0xFF));
+    assert_eq!(row.plane(Plane::BlackWhite), [0xFF; ROW_PLANE_BYTES]);
+    assert_eq!(row.packed()[0], 0b1101_0101);
+
+    for y in [0, 2] {
+        let row = display.rows().nth(y).unwrap();
+        assert_eq!(row.plane(Plane::Red), [0xFF; ROW_PLANE_BYTES]);
+    }
+}
+
+#[test]
+fn blend_toward_steps_through_inks_by_coverage() {
+    use QuadColor::*;
+    for (coverage, ink) in [
+        (0, White),
+        (42, White),
+        (43, Yellow),
+        (127, Yellow),
+        (128, Red),
+        (212, Red),
+        (213, Black),
+        (255, Black),
+    ] {
+        assert_eq!(White.blend_toward(Black, coverage), ink, "{coverage}");
+    }
+    assert_eq!(Black.blend_toward(White, 43), Red);
+    assert_eq!(Red.blend_toward(Yellow, 127), Red);
+    assert_eq!(Red.blend_toward(Yellow, 128), Yellow);
+    assert_eq!(Red.blend_toward(Red, 200), Red);
+}
@@ -0,0 +1,41 @@
+//! One-call text display for quick demos.
+
+use embedded_graphics::mono_font::ascii::FONT_6X10;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::{Baseline, Text};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::spi::SpiDevice;
+
+use crate::{DisplayBuffer, Jd79661, Jd79661Error, QuadColor};
+
+impl<CS, BUSY, DC, RST> Jd79661<CS, BUSY, DC, RST>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    /// Clears the panel to white and shows `text` in the 6x10 font with its
+    /// top-left corner at `origin`, then does a full refresh.
+    ///
+    /// `\n` starts a new line. The frame is built in a temporary
+    /// [`DisplayBuffer`] on the stack (about 11 KiB); keep your own buffer and
+    /// call [`update_frames`](Self::update_frames) for anything more elaborate.
+    pub fn show_text<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        text: &str,
+        origin: Point,
+        color: QuadColor,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        let mut display = DisplayBuffer::new();
+        let style = MonoTextStyle::new(&FONT_6X10, color);
+        let _ = Text::with_baseline(text, origin, style, Baseline::Top).draw(&mut display);
+
+        self.update_frames(spi, &display)?;
+        self.display_frame(spi, delay)
+    }
+}
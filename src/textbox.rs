@@ -0,0 +1,36 @@
+//! Word-wrapped paragraphs via `embedded-text` (`textbox` feature).
+//!
+//! [`DisplayBuffer`] is a plain `DrawTarget<Color = QuadColor>`, so any
+//! `embedded_text::TextBox` can be drawn into it directly. This module only
+//! adds a shortcut for the common case and re-exports the crate.
+
+pub use embedded_text;
+
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_text::alignment::HorizontalAlignment;
+use embedded_text::TextBox;
+
+use crate::{DisplayBuffer, QuadColor};
+
+impl DisplayBuffer {
+    /// Draws `text` word-wrapped inside `bounds` and returns the part that did
+    /// not fit.
+    ///
+    /// Lines are broken on whitespace, `\n` forces a break and anything below
+    /// the bottom edge is left undrawn. Feed the returned slice into the next
+    /// box (or page) to continue the text.
+    pub fn draw_text_box<'a>(
+        &mut self,
+        text: &'a str,
+        bounds: Rectangle,
+        style: MonoTextStyle<'_, QuadColor>,
+        alignment: HorizontalAlignment,
+    ) -> &'a str {
+        match TextBox::with_alignment(text, bounds, style, alignment).draw(self) {
+            Ok(rest) => rest,
+            Err(never) => match never {},
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Several panels driven as one larger drawing surface.
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::spi::SpiDevice;
+
+use crate::{DisplayBuffer, Jd79661, Jd79661Error, QuadColor, HEIGHT, WIDTH};
+
+/// `N` panels, each with its own CS, tiled into one `DrawTarget`.
+///
+/// Every tile keeps its own [`DisplayBuffer`] and sits at an offset in the
+/// combined coordinate space; pixels are routed to whichever tile covers them
+/// and anything outside all tiles is dropped. Since each driver toggles its
+/// own CS, all panels can share one `SpiDevice`.
+///
+/// All tiles share one set of pin types. On the rp2040 each GPIO is a type
+/// of its own, so erase them with `into_dyn_pin` before building the drivers,
+/// e.g. for the 1x2 layout with a second panel on GPIO 9 to 12:
+///
+/// ```ignore
+/// let left = Jd79661::new(
+///     &mut spi_device,
+///     pins.gpio19.into_push_pull_output().into_dyn_pin(), // CS
+///     pins.gpio16.into_pull_down_input().into_dyn_pin(),  // BUSY
+///     pins.gpio18.into_push_pull_output().into_dyn_pin(), // DC
+///     pins.gpio17.into_push_pull_output().into_dyn_pin(), // RST
+///     &mut delay,
+/// )?;
+/// let right = Jd79661::new(
+///     &mut spi_device,
+///     pins.gpio10.into_push_pull_output().into_dyn_pin(),
+///     pins.gpio12.into_pull_down_input().into_dyn_pin(),
+///     pins.gpio9.into_push_pull_output().into_dyn_pin(),
+///     pins.gpio11.into_push_pull_output().into_dyn_pin(),
+///     &mut delay,
+/// )?;
+/// let mut wall = TiledDisplay::horizontal([left, right]);
+/// ```
+pub struct TiledDisplay<CS, BUSY, DC, RST, const N: usize> {
+    panels: [Jd79661<CS, BUSY, DC, RST>; N],
+    buffers: [DisplayBuffer; N],
+    offsets: [Point; N],
+}
+
+impl<CS, BUSY, DC, RST, const N: usize> TiledDisplay<CS, BUSY, DC, RST, N>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    /// Tiles `panels` with the top-left corner of panel `i` at `offsets[i]`.
+    ///
+    /// Offsets should not be negative. Overlapping tiles both receive the
+    /// pixels they share.
+    pub fn new(panels: [Jd79661<CS, BUSY, DC, RST>; N], offsets: [Point; N]) -> Self {
+        Self {
+            panels,
+            buffers: core::array::from_fn(|_| DisplayBuffer::new()),
+            offsets,
+        }
+    }
+
+    /// Tiles `panels` left to right in a single row, panel 0 leftmost.
+    pub fn horizontal(panels: [Jd79661<CS, BUSY, DC, RST>; N]) -> Self {
+        let offsets = core::array::from_fn(|i| Point::new((i * WIDTH) as i32, 0));
+        Self::new(panels, offsets)
+    }
+
+    /// Gives back the drivers, in tile order.
+    pub fn release(self) -> [Jd79661<CS, BUSY, DC, RST>; N] {
+        self.panels
+    }
+
+    /// The driver of tile `index`, for per-panel calls such as partial updates.
+    pub fn panel_mut(&mut self, index: usize) -> &mut Jd79661<CS, BUSY, DC, RST> {
+        &mut self.panels[index]
+    }
+
+    /// The buffer of tile `index`, in that panel's own coordinates.
+    pub fn buffer(&self, index: usize) -> &DisplayBuffer {
+        &self.buffers[index]
+    }
+
+    pub fn buffer_mut(&mut self, index: usize) -> &mut DisplayBuffer {
+        &mut self.buffers[index]
+    }
+
+    /// Area tile `index` covers in the combined coordinate space.
+    pub fn tile_area(&self, index: usize) -> Rectangle {
+        Rectangle::new(self.offsets[index], Size::new(WIDTH as u32, HEIGHT as u32))
+    }
+
+    /// Sends every tile's buffer to its panel; returns the total bytes written.
+    pub fn update_frames<SPI: SpiDevice>(
+        &mut self,
+        spi: &mut SPI,
+    ) -> Result<usize, Jd79661Error<SPI::Error>> {
+        let mut written = 0;
+        for (panel, buffer) in self.panels.iter_mut().zip(&self.buffers) {
+            written += panel.update_frames(spi, buffer)?;
+        }
+        Ok(written)
+    }
+
+    /// Runs a full refresh on each panel in tile order.
+    ///
+    /// Each refresh blocks until that panel's BUSY clears, so the whole wall
+    /// takes about `N` times as long as a single panel.
+    pub fn display_frame<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        for panel in &mut self.panels {
+            panel.display_frame(spi, delay)?;
+        }
+        Ok(())
+    }
+
+    /// [`update_frames`](Self::update_frames) followed by
+    /// [`display_frame`](Self::display_frame).
+    pub fn refresh<SPI: SpiDevice, DELAY: DelayNs>(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), Jd79661Error<SPI::Error>> {
+        self.update_frames(spi)?;
+        self.display_frame(spi, delay)
+    }
+}
+
+impl<CS, BUSY, DC, RST, const N: usize> DrawTarget for TiledDisplay<CS, BUSY, DC, RST, N>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin + StatefulOutputPin,
+{
+    type Color = QuadColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            for i in 0..N {
+                if self.tile_area(i).contains(point) {
+                    let local = Pixel(point - self.offsets[i], color);
+                    self.buffers[i].draw_iter(core::iter::once(local))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for i in 0..N {
+            let overlap = area.intersection(&self.tile_area(i));
+            if !overlap.is_zero_sized() {
+                let local = overlap.translate(Point::zero() - self.offsets[i]);
+                self.buffers[i].fill_solid(&local, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        for buffer in &mut self.buffers {
+            buffer.fill(color);
+        }
+        Ok(())
+    }
+}
+
+impl<CS, BUSY, DC, RST, const N: usize> OriginDimensions for TiledDisplay<CS, BUSY, DC, RST, N> {
+    /// The bounding box of all tiles, measured from the origin.
+    fn size(&self) -> Size {
+        self.offsets.iter().fold(Size::zero(), |size, offset| {
+            Size::new(
+                size.width.max(offset.x.max(0) as u32 + WIDTH as u32),
+                size.height.max(offset.y.max(0) as u32 + HEIGHT as u32),
+            )
+        })
+    }
+}
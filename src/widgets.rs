@@ -0,0 +1,363 @@
+//! Ready-made UI elements drawn straight into a [`DisplayBuffer`].
+
+use core::fmt::Write;
+
+use embedded_graphics::image::{Image, ImageRaw};
+use embedded_graphics::mono_font::ascii::FONT_4X6;
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+
+use heapless::String;
+
+use crate::{DisplayBuffer, QuadColor, HEIGHT, WIDTH};
+
+/// Reading direction of [`DisplayBuffer::draw_vertical_text`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TextDirection {
+    /// Rotated 90° clockwise: reads downwards with the glyph tops facing right.
+    TopToBottom,
+    /// Rotated 90° counterclockwise: reads upwards with the glyph tops facing
+    /// left.
+    BottomToTop,
+}
+
+impl DisplayBuffer {
+    /// Draws `text` in `font`, rotated to run vertically, with the top-left
+    /// corner of the rotated text box at `origin`. Returns the area covered.
+    ///
+    /// The text is laid out by embedded-graphics as usual (advance, character
+    /// spacing and `\n` line breaks), then each glyph pixel is rotated, so a
+    /// line's advance runs along the y axis and successive lines stack
+    /// sideways away from the first. Only the glyphs are drawn; the background
+    /// is left untouched.
+    pub fn draw_vertical_text(
+        &mut self,
+        text: &str,
+        origin: Point,
+        font: &MonoFont<'_>,
+        color: QuadColor,
+        direction: TextDirection,
+    ) -> Rectangle {
+        let style = MonoTextStyle::new(font, color);
+        let text = Text::with_baseline(text, Point::zero(), style, Baseline::Top);
+        let size = text.bounding_box().size;
+
+        let mut rotated = Rotated {
+            target: self,
+            origin,
+            size,
+            direction,
+        };
+        let _ = text.draw(&mut rotated);
+
+        Rectangle::new(origin, Size::new(size.height, size.width))
+    }
+
+    /// Draws `text` horizontally centered on the panel with its top edge at
+    /// `y`, and returns the area it covers.
+    ///
+    /// The width is measured from the style's font (the widest line, for
+    /// multi-line text, with lines left-aligned under each other). Text wider
+    /// than [`WIDTH`] starts at x = 0 and is clipped on the right.
+    pub fn draw_centered_text(
+        &mut self,
+        text: &str,
+        y: i32,
+        style: MonoTextStyle<'_, QuadColor>,
+    ) -> Rectangle {
+        let width = Text::with_baseline(text, Point::zero(), style, Baseline::Top)
+            .bounding_box()
+            .size
+            .width;
+        let x = (WIDTH as u32).saturating_sub(width) / 2;
+        let text = Text::with_baseline(text, Point::new(x as i32, y), style, Baseline::Top);
+        let _ = text.draw(self);
+        text.bounding_box()
+    }
+
+    /// Stamps a 1-bit image with its top-left corner at `origin`: set bits are
+    /// drawn in `on`, clear bits in `off`, or left untouched if `off` is
+    /// `None`. Parts outside the buffer are clipped.
+    pub fn draw_mono_image(
+        &mut self,
+        raw: &ImageRaw<'_, BinaryColor>,
+        origin: Point,
+        on: QuadColor,
+        off: Option<QuadColor>,
+    ) {
+        let mut mapped = MonoMapped {
+            target: self,
+            on,
+            off,
+        };
+        let _ = Image::new(raw, origin).draw(&mut mapped);
+    }
+
+    /// Draws a 1 px `border` around `rect` and fills its inside from the left
+    /// in proportion to `fraction` (clamped to `0.0..=1.0`), e.g. for progress or
+    /// battery level.
+    ///
+    /// The unfilled part is cleared to white so the bar can be redrawn with a
+    /// lower value. Rectangles too small to have an inside only get the border.
+    pub fn draw_progress_bar(
+        &mut self,
+        rect: Rectangle,
+        fraction: f32,
+        fill: QuadColor,
+        border: QuadColor,
+    ) {
+        if rect.is_zero_sized() {
+            return;
+        }
+        self.draw_outline(rect, border);
+
+        let inner = rect.offset(-1);
+        if inner.is_zero_sized() {
+            return;
+        }
+        // NaN stays NaN through the clamp and casts to 0.
+        let fraction = fraction.clamp(0.0, 1.0);
+        let filled = (inner.size.width as f32 * fraction + 0.5) as u32;
+
+        self.fill_rect(
+            Rectangle::new(inner.top_left, Size::new(filled, inner.size.height)),
+            fill,
+        );
+        self.fill_rect(
+            Rectangle::new(
+                inner.top_left + Point::new(filled as i32, 0),
+                Size::new(inner.size.width - filled, inner.size.height),
+            ),
+            QuadColor::White,
+        );
+    }
+
+    /// Draws a 1 px horizontal dashed line of `length` pixels starting at
+    /// `start` and running right: `on` pixels of `color`, then `off` pixels
+    /// left untouched, repeated.
+    ///
+    /// An `on` of 0 draws nothing and an `off` of 0 draws a solid line.
+    pub fn hline_dashed(&mut self, start: Point, length: u32, on: u32, off: u32, color: QuadColor) {
+        for (offset, len) in dashes(length, on, off) {
+            self.fill_rect(
+                Rectangle::new(start + Point::new(offset as i32, 0), Size::new(len, 1)),
+                color,
+            );
+        }
+    }
+
+    /// Like [`hline_dashed`](Self::hline_dashed), but running down from
+    /// `start`.
+    pub fn vline_dashed(&mut self, start: Point, length: u32, on: u32, off: u32, color: QuadColor) {
+        for (offset, len) in dashes(length, on, off) {
+            self.fill_rect(
+                Rectangle::new(start + Point::new(0, offset as i32), Size::new(1, len)),
+                color,
+            );
+        }
+    }
+
+    /// Draws the 1 px dashed outline of `rect`. Each side is dashed from its
+    /// top or left end, so the corners are always drawn when `on` is nonzero.
+    pub fn rect_dashed(&mut self, rect: Rectangle, on: u32, off: u32, color: QuadColor) {
+        if rect.is_zero_sized() {
+            return;
+        }
+        let Rectangle { top_left, size } = rect;
+        let bottom = top_left.y + size.height as i32 - 1;
+        let right = top_left.x + size.width as i32 - 1;
+
+        self.hline_dashed(top_left, size.width, on, off, color);
+        self.hline_dashed(Point::new(top_left.x, bottom), size.width, on, off, color);
+        self.vline_dashed(top_left, size.height, on, off, color);
+        self.vline_dashed(Point::new(right, top_left.y), size.height, on, off, color);
+    }
+
+    /// Draws a seven-segment digit of `size` with its top-left corner at
+    /// `origin`, built from filled rectangles so it stays crisp at any height.
+    ///
+    /// `glyph` is an ASCII character, so the bytes of a formatted number can be
+    /// passed straight in: `b'0'..=b'9'` draw that digit, `b'-'` the middle
+    /// segment only, and `b' '` or any other byte nothing. Segments are about a fifth of the digit's width
+    /// thick and only the lit ones are drawn; fill the area first to redraw
+    /// over an old digit.
+    pub fn draw_seven_segment(&mut self, glyph: u8, origin: Point, size: Size, color: QuadColor) {
+        // Segments a to g in bits 6 to 0: top, top right, bottom right,
+        // bottom, bottom left, top left, middle.
+        const DIGITS: [u8; 10] = [
+            0b111_1110, 0b011_0000, 0b110_1101, 0b111_1001, 0b011_0011, 0b101_1011, 0b101_1111,
+            0b111_0000, 0b111_1111, 0b111_1011,
+        ];
+        let lit = match glyph {
+            b'0'..=b'9' => DIGITS[(glyph - b'0') as usize],
+            b'-' => 0b000_0001,
+            _ => return,
+        };
+
+        let Size {
+            width: w,
+            height: h,
+        } = size;
+        let t = (w.min(h / 2) / 5).max(1);
+        let mid = h.saturating_sub(t) / 2;
+        let across = w.saturating_sub(2 * t);
+        let upper = mid.saturating_sub(t);
+        let lower = h.saturating_sub(mid + 2 * t);
+        let right = w.saturating_sub(t) as i32;
+        let (t_i, mid_i) = (t as i32, mid as i32);
+
+        let segments = [
+            (Point::new(t_i, 0), Size::new(across, t)),
+            (Point::new(right, t_i), Size::new(t, upper)),
+            (Point::new(right, mid_i + t_i), Size::new(t, lower)),
+            (
+                Point::new(t_i, h.saturating_sub(t) as i32),
+                Size::new(across, t),
+            ),
+            (Point::new(0, mid_i + t_i), Size::new(t, lower)),
+            (Point::new(0, t_i), Size::new(t, upper)),
+            (Point::new(t_i, mid_i), Size::new(across, t)),
+        ];
+        for (i, (offset, size)) in segments.into_iter().enumerate() {
+            if lit & (0b100_0000 >> i) != 0 {
+                self.fill_rect(Rectangle::new(origin + offset, size), color);
+            }
+        }
+    }
+
+    /// Draws a layout reference grid: dotted lines every `spacing` pixels,
+    /// with the x coordinates labelled along the top edge and y along the left
+    /// edge in a 4x6 font.
+    ///
+    /// Labels go on every line with a `spacing` of 24 px or more, and on fewer
+    /// lines otherwise so they don't run into each other. A `spacing` of 0
+    /// draws nothing.
+    pub fn draw_grid(&mut self, spacing: u32, color: QuadColor) {
+        if spacing == 0 {
+            return;
+        }
+        let label_every = spacing * 24u32.div_ceil(spacing);
+        let style = MonoTextStyle::new(&FONT_4X6, color);
+        let mut label: String<4> = String::new();
+
+        for x in (spacing..WIDTH as u32).step_by(spacing as usize) {
+            self.vline_dashed(Point::new(x as i32, 0), HEIGHT as u32, 1, 2, color);
+            if x % label_every == 0 {
+                label.clear();
+                let _ = write!(label, "{}", x);
+                let origin = Point::new(x as i32 + 2, 1);
+                let _ = Text::with_baseline(&label, origin, style, Baseline::Top).draw(self);
+            }
+        }
+        for y in (spacing..HEIGHT as u32).step_by(spacing as usize) {
+            self.hline_dashed(Point::new(0, y as i32), WIDTH as u32, 1, 2, color);
+            if y % label_every == 0 {
+                label.clear();
+                let _ = write!(label, "{}", y);
+                let origin = Point::new(1, y as i32 + 2);
+                let _ = Text::with_baseline(&label, origin, style, Baseline::Top).draw(self);
+            }
+        }
+    }
+
+    /// Draws the 1 px outline of `rect`.
+    pub(crate) fn draw_outline(&mut self, rect: Rectangle, color: QuadColor) {
+        let Rectangle { top_left, size } = rect;
+        let bottom = top_left.y + size.height as i32 - 1;
+        let right = top_left.x + size.width as i32 - 1;
+
+        self.fill_rect(Rectangle::new(top_left, Size::new(size.width, 1)), color);
+        self.fill_rect(
+            Rectangle::new(Point::new(top_left.x, bottom), Size::new(size.width, 1)),
+            color,
+        );
+        self.fill_rect(Rectangle::new(top_left, Size::new(1, size.height)), color);
+        self.fill_rect(
+            Rectangle::new(Point::new(right, top_left.y), Size::new(1, size.height)),
+            color,
+        );
+    }
+}
+
+/// Yields `(offset, len)` of each dash along a line of `length` pixels.
+fn dashes(length: u32, on: u32, off: u32) -> impl Iterator<Item = (u32, u32)> {
+    let period = on.saturating_add(off).max(1);
+    let length = if on == 0 { 0 } else { length };
+    (0..length)
+        .step_by(period as usize)
+        .map(move |offset| (offset, on.min(length - offset)))
+}
+
+/// Draws text-space pixels (x along the line, y down the glyphs) rotated into
+/// a [`DisplayBuffer`] for [`DisplayBuffer::draw_vertical_text`].
+struct Rotated<'a> {
+    target: &'a mut DisplayBuffer,
+    origin: Point,
+    /// Size of the unrotated text box.
+    size: Size,
+    direction: TextDirection,
+}
+
+impl DrawTarget for Rotated<'_> {
+    type Color = QuadColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (width, height) = (self.size.width as i32, self.size.height as i32);
+        let (origin, direction) = (self.origin, self.direction);
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(p, color)| {
+                let offset = match direction {
+                    TextDirection::TopToBottom => Point::new(height - 1 - p.y, p.x),
+                    TextDirection::BottomToTop => Point::new(p.y, width - 1 - p.x),
+                };
+                Pixel(origin + offset, color)
+            }))
+    }
+}
+
+impl OriginDimensions for Rotated<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// Maps [`BinaryColor`] pixels onto a [`DisplayBuffer`] for
+/// [`DisplayBuffer::draw_mono_image`].
+struct MonoMapped<'a> {
+    target: &'a mut DisplayBuffer,
+    on: QuadColor,
+    off: Option<QuadColor>,
+}
+
+impl DrawTarget for MonoMapped<'_> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (on, off) = (self.on, self.off);
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter_map(|Pixel(p, color)| match color {
+                    BinaryColor::On => Some(Pixel(p, on)),
+                    BinaryColor::Off => off.map(|off| Pixel(p, off)),
+                }),
+        )
+    }
+}
+
+impl OriginDimensions for MonoMapped<'_> {
+    fn size(&self) -> Size {
+        self.target.size()
+    }
+}